@@ -24,6 +24,38 @@ use serde::{de, ser};
 use std::str::Utf8Error;
 use std::{error, fmt, io, result};
 
+/// A segment of the path to a value, which failed to deserialize.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PathSegment {
+    /// A field of a struct (or struct variant).
+    Field(&'static str),
+
+    /// The index of an element of a seq or tuple.
+    Index(usize),
+
+    /// The key of a map entry, if it is a string or an integer.
+    Key(String),
+
+    /// The index of a map entry, whose key is neither a string nor an
+    /// integer.
+    Entry(usize),
+
+    /// The index of an enum variant.
+    Variant(u32),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(fmt, ".{}", name),
+            PathSegment::Index(n) => write!(fmt, "[{}]", n),
+            PathSegment::Key(key) => write!(fmt, "[{:?}]", key),
+            PathSegment::Entry(n) => write!(fmt, "{{{}}}", n),
+            PathSegment::Variant(n) => write!(fmt, "::{}", n),
+        }
+    }
+}
+
 /// Errors thrown by the `bytes` modules.
 #[derive(Debug)]
 pub enum Error {
@@ -39,12 +71,84 @@ pub enum Error {
     /// But the `u32` is not a char.
     InvalidChar(u32),
 
+    /// In strict mode the given `u8` value is not a valid `bool`.
+    InvalidBool(u8),
+
+    /// In strict mode the given `u8` value is not a valid tag of an option.
+    InvalidOptionTag(u8),
+
+    /// In self-describing mode the given `u8` value is not a type tag.
+    InvalidType(u8),
+
+    /// A varint is not encoded correctly.
+    ///
+    /// Either the encoding is overlong or the value does not fit into the
+    /// requested integer type.
+    InvalidVarint,
+
     /// Failed to deserialize into a string. The source byte data are not valid
     /// UTF-8.
     InvalidString(Utf8Error),
 
-    /// The length is unknown when serializing a sequence or map.
-    RequiredLength,
+    /// The length of a string, byte array, sequence or map does not fit into
+    /// the configured length prefix.
+    LengthOverflow(u64),
+
+    /// The index of an enum variant does not fit into the configured tag.
+    TagOverflow(u32),
+
+    /// The decoded tag is not a valid index of an enum variant.
+    InvalidTag(u64),
+
+    /// A limit configured for the reader is exceeded.
+    ///
+    /// The argument is the requested size, which exceeds the limit.
+    LimitExceeded(u64),
+
+    /// A decoded length does not fit into an `usize` of the target platform.
+    LengthTooLarge(u64),
+
+    /// A length-prefixed value consumed more bytes than its length prefix.
+    ///
+    /// The argument is the length of the value.
+    Overrun(usize),
+
+    /// The nesting depth of a value exceeds the configured maximum.
+    ///
+    /// The argument is the maximum depth.
+    DepthExceeded(usize),
+
+    /// A map value was serialized without serializing its key first.
+    MissingKey,
+
+    /// A map key was serialized without serializing its value.
+    MissingValue,
+
+    /// Deserialization is finished but the source still has data.
+    ///
+    /// The argument is the number of bytes left (see
+    /// [`TakeBytes::remaining`]).
+    ///
+    /// [`TakeBytes::remaining`]: crate::TakeBytes::remaining
+    TrailingBytes(usize),
+
+    /// The requested Serde operation is not supported by the format.
+    ///
+    /// The argument names the unsupported operation.
+    Unsupported(&'static str),
+
+    /// An error occured at the given position of a [`Reader`].
+    ///
+    /// The `position` is the number of bytes consumed by the reader, when the
+    /// error `cause` occured. The `path` points to the value, which failed to
+    /// deserialize, starting at the outermost value.
+    ///
+    /// [`Reader`]: crate::Reader
+    Context {
+        position: usize,
+        path: Vec<PathSegment>,
+        cause: Box<Error>,
+    },
 
     /// An I/O error occured.
     Io(io::Error),
@@ -71,6 +175,76 @@ impl Error {
     pub fn other<E: Into<Box<dyn error::Error + Send + Sync>>>(err: E) -> Error {
         Error::Other(err.into())
     }
+
+    /// Returns the position, where the error occured.
+    ///
+    /// Only an [`Error::Context`] error has a position.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            Error::Context { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// Returns the path to the value, where the error occured.
+    ///
+    /// Only an [`Error::Context`] error has a path, for any other error an
+    /// empty path is returned.
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            Error::Context { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    /// Returns the error without its context.
+    ///
+    /// For an [`Error::Context`] error this is the wrapped cause, any other
+    /// error is returned unchanged.
+    pub fn inner(&self) -> &Error {
+        match self {
+            Error::Context { cause, .. } => cause,
+            _ => self,
+        }
+    }
+
+    /// Consumes the error, returning the error without its context.
+    ///
+    /// See [`Error::inner`] for more information.
+    pub fn into_inner(self) -> Error {
+        match self {
+            Error::Context { cause, .. } => *cause,
+            _ => self,
+        }
+    }
+
+    /// Wraps the error into an [`Error::Context`] error.
+    ///
+    /// An error which already has a context is returned unchanged.
+    pub(crate) fn at(self, position: usize) -> Error {
+        match self {
+            Error::Context { .. } => self,
+            _ => Error::Context {
+                position,
+                path: vec![],
+                cause: Box::new(self),
+            },
+        }
+    }
+
+    /// Prepends the given `segment` to the path of the error.
+    ///
+    /// If necessary the error is wrapped into an [`Error::Context`] error
+    /// first.
+    pub(crate) fn in_path(self, position: usize, segment: PathSegment) -> Error {
+        let mut err = self.at(position);
+
+        if let Error::Context { path, .. } = &mut err {
+            path.insert(0, segment);
+        }
+
+        err
+    }
 }
 
 impl fmt::Display for Error {
@@ -79,8 +253,40 @@ impl fmt::Display for Error {
             Error::Eof(_) => write!(fmt, "No more bytes are available for reading."),
             Error::NoSpace(_) => write!(fmt, "no more space available for writing"),
             Error::InvalidChar(n) => write!(fmt, "not a char: {}", n),
+            Error::InvalidBool(n) => write!(fmt, "not a bool: {}", n),
+            Error::InvalidOptionTag(n) => write!(fmt, "not an option tag: {}", n),
+            Error::InvalidType(n) => write!(fmt, "not a type tag: {}", n),
+            Error::InvalidVarint => write!(fmt, "invalid varint"),
             Error::InvalidString(cause) => write!(fmt, "not a string: {}", cause),
-            Error::RequiredLength => write!(fmt, "the length of the sequence or map is required"),
+            Error::LengthOverflow(n) => {
+                write!(fmt, "the length {} does not fit into the prefix", n)
+            }
+            Error::TagOverflow(n) => {
+                write!(fmt, "the variant index {} does not fit into the tag", n)
+            }
+            Error::InvalidTag(n) => write!(fmt, "not a variant index: {}", n),
+            Error::LimitExceeded(n) => write!(fmt, "{} exceeds the configured limit", n),
+            Error::LengthTooLarge(n) => write!(fmt, "the length {} does not fit into an usize", n),
+            Error::Overrun(n) => write!(fmt, "the value exceeds its length of {} bytes", n),
+            Error::DepthExceeded(n) => write!(fmt, "the maximum depth of {} is exceeded", n),
+            Error::MissingKey => write!(fmt, "the map value has no key"),
+            Error::MissingValue => write!(fmt, "the map key has no value"),
+            Error::TrailingBytes(n) => write!(fmt, "{} trailing bytes left", n),
+            Error::Unsupported(what) => write!(fmt, "{} is not supported", what),
+            Error::Context {
+                position,
+                path,
+                cause,
+            } => {
+                write!(fmt, "at byte {}", position)?;
+
+                if !path.is_empty() {
+                    let path = path.iter().map(|s| s.to_string()).collect::<String>();
+                    write!(fmt, " ({})", path.trim_start_matches('.'))?;
+                }
+
+                write!(fmt, ": {}", cause)
+            }
             Error::Io(cause) => fmt::Display::fmt(cause, fmt),
             Error::Serde(msg) => fmt::Display::fmt(msg, fmt),
             Error::Other(cause) => fmt::Display::fmt(cause, fmt),
@@ -94,6 +300,7 @@ impl error::Error for Error {
             Error::Eof(Some(cause)) => Some(cause.as_ref()),
             Error::NoSpace(Some(cause)) => Some(cause.as_ref()),
             Error::InvalidString(cause) => Some(cause),
+            Error::Context { cause, .. } => Some(cause.as_ref()),
             Error::Io(cause) => Some(cause),
             Error::Other(cause) => Some(cause.as_ref()),
             _ => None,
@@ -37,9 +37,14 @@
 //!    trait and finally
 //! 2. performs the deserialization.
 //!
-//! The crate implements [`TakeBytes`] already for
-//! [`&[u8]`](trait.TakeBytes.html#impl-TakeBytes%3C%27tb%3E-for-%26%27tb%20%5Bu8%5D).
-//! It takes bytes from a [slice] of `u8` values.
+//! The crate implements [`TakeBytes`] for the following types:
+//!
+//! * [`&[u8]`](trait.TakeBytes.html#impl-TakeBytes%3C%27tb%3E-for-%26%27tb%20%5Bu8%5D)
+//!   Takes bytes from a [slice] of `u8` values.
+//! * [`ReadSource`]
+//!   Takes bytes from a type that implements [`std::io::Read`]. This allows
+//!   deserialization directly from a file or socket without reading all data
+//!   into memory first.
 //!
 //! ## Deserialization example
 //!
@@ -71,6 +76,10 @@
 //! assert_eq!(sample, SampleStruct{ f1: 7, f2: 666 });
 //! assert_eq!(*reader.as_ref(), [0x00]); // Still one byte left
 //!
+//! // finish() rejects the trailing byte
+//! let err = reader.finish().unwrap_err();
+//! assert_eq!(format!("{}", err), "at byte 3: 1 trailing bytes left");
+//!
 //! // deserialize an enum
 //! let mut reader = Reader::new([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x9A].as_slice());
 //!
@@ -81,7 +90,7 @@
 //! let mut reader = Reader::new([0; 3].as_slice());
 //! let err = reader.deserialize::<u32>().unwrap_err();
 //!
-//! assert_eq!(format!("{}", err), "No more bytes are available for reading.");
+//! assert_eq!(format!("{}", err), "at byte 0: No more bytes are available for reading.");
 //! ```
 //!
 //! # Serialization into a binary representation
@@ -104,6 +113,16 @@
 //! * [`Vec<u8>`](trait.PutBytes.html#impl-PutBytes-for-Vec<u8>)
 //!   Serialize into a [`Vec`] of `u8` values. The binary data are appended to
 //!   the [`Vec`].
+//! * [`WriteTarget`]
+//!   Serialize into a type that implements [`std::io::Write`]. This allows
+//!   serialization directly into a file or socket without an intermediate
+//!   buffer. If the writer is not able to accept all data, an
+//!   [`Error::NoSpace`] error is raised.
+//!
+//! A seq or map with an unknown length (e.g. an iterator serialized with
+//! [`Serializer::collect_seq`](serde::Serializer::collect_seq)) is supported.
+//! If the target supports patching (see [`PutBytes::patch_position`]) the
+//! length is filled in afterwards, otherwise the elements are buffered.
 //!
 //! ## Serialization examples
 //!
@@ -203,6 +222,100 @@
 //! assert_eq!(format!("{}", err), "no more space available for writing");
 //! ```
 //!
+//! # Errors
+//!
+//! Errors returned by a [`Reader`] are wrapped into an [`Error::Context`]
+//! error, which carries the position of the reader (the number of consumed
+//! bytes), where the error occured, and the path to the value, which failed
+//! to deserialize. Use [`Error::position`] and [`Error::path`] to get them and
+//! [`Error::inner`] or [`Error::into_inner`] to get the wrapped error. The
+//! position of a [`Writer`] is available with [`Writer::position`].
+//!
+//! ```rust
+//! use nuts_bytes::{Error, Reader};
+//!
+//! let mut reader = Reader::new([0x00, 0x01, 0x02].as_slice());
+//! assert_eq!(reader.read_u16().unwrap(), 1);
+//!
+//! let err = reader.read_u16().unwrap_err();
+//! assert_eq!(err.position(), Some(2));
+//! assert!(matches!(err.inner(), Error::Eof(_)));
+//! ```
+//!
+//! # Options
+//!
+//! The binary format can be customized with [`Options`]. Create a [`Reader`]
+//! with [`Options::build_reader`] and a [`Writer`] with
+//! [`Options::build_writer`]. [`Reader::new`] and [`Writer::new`] use the
+//! default options.
+//!
+//! * Multi-byte values (integers, floats, length prefixes and variant
+//!   indices) are encoded in big endian by default. Use
+//!   [`Options::with_little_endian`] to switch to little endian.
+//! * Integers are encoded with a fixed size by default. Use
+//!   [`Options::with_varint_prefixes`] or [`Options::with_varint`] to encode
+//!   (some) integers as varints.
+//! * The length of a string, byte array, seq or map is encoded as an `u64`
+//!   value by default. Use [`Options::with_length_width`] to choose a smaller
+//!   prefix.
+//! * The index of an enum variant is encoded as an `u32` value by default.
+//!   Use [`Options::with_tag_width`] to choose another tag width or
+//!   [`Options::with_variant_names`] to encode the name of the variant.
+//! * The fields of a struct are encoded one after the other by default. Use
+//!   [`Options::with_field_names`] or [`Options::with_field_indices`] to tag
+//!   every field with its name or index.
+//! * No envelopes are encoded by default. Use [`Options::with_envelope`] to
+//!   prefix structs (and enum payloads) with their encoded length.
+//! * A reader has no limits by default. Use [`Options::with_item_limit`] and
+//!   [`Options::with_total_limit`] when reading untrusted data.
+//! * A reader accepts any non-zero value as `true` or as `Some` tag by
+//!   default. Use [`Options::with_strict`] to accept only canonical
+//!   encodings.
+//! * The nesting depth is not limited by default. Use
+//!   [`Options::with_max_depth`] to limit the depth of nested values.
+//! * The format is not self-describing by default. Use
+//!   [`Options::with_self_describing`] to prefix every value with a type tag.
+//!   This enables types which need to inspect the binary data (e.g. untagged
+//!   enums).
+//!
+//! # Convenience functions
+//!
+//! For the common cases the crate provides functions, which create the
+//! [`Reader`] or [`Writer`] for you:
+//!
+//! * [`from_slice`] deserializes from a [slice] of `u8` values. All bytes of
+//!   the slice must be consumed; [`take_from_slice`] also returns the unread
+//!   part of the slice.
+//! * [`from_reader`] deserializes from a [`std::io::Read`] instance.
+//! * [`to_vec`] serializes into a [`Vec`].
+//! * [`to_writer`] serializes into a [`std::io::Write`] instance.
+//! * [`serialized_size`] calculates the number of bytes required to serialize
+//!   a value without writing it anywhere.
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Deserialize, PartialEq, Serialize)]
+//! struct SampleStruct {
+//!     f1: u8,
+//!     f2: u16,
+//! };
+//!
+//! let sample = SampleStruct{ f1: 7, f2: 666 };
+//!
+//! assert_eq!(nuts_bytes::serialized_size(&sample).unwrap(), 3);
+//!
+//! let vec = nuts_bytes::to_vec(&sample).unwrap();
+//! assert_eq!(vec, [0x07, 0x02, 0x9A]);
+//!
+//! let sample2: SampleStruct = nuts_bytes::from_slice(&vec).unwrap();
+//! assert_eq!(sample2, sample);
+//!
+//! // Trailing bytes are rejected
+//! let err = nuts_bytes::from_slice::<SampleStruct>(&[0x07, 0x02, 0x9A, 0x00]).unwrap_err();
+//! assert_eq!(format!("{}", err), "at byte 3: 1 trailing bytes left");
+//! ```
+//!
 //! # Format specification
 //!
 //! The binary format is described [here](doc_format) in detail.
@@ -211,8 +324,10 @@
 //! [Format specification]: #format-specification
 
 mod error;
+mod options;
 mod reader;
 mod source;
+mod tag;
 mod target;
 mod writer;
 
@@ -226,11 +341,12 @@ pub mod doc_format {
 #[cfg(doc)]
 use serde::{Deserialize, Serialize};
 
-pub use error::{Error, Result};
-pub use reader::Reader;
-pub use source::TakeBytes;
-pub use target::PutBytes;
-pub use writer::Writer;
+pub use error::{Error, PathSegment, Result};
+pub use options::{Endian, Envelope, Fields, Int, Options, Width};
+pub use reader::{from_reader, from_slice, take_from_slice, Reader};
+pub use source::{ReadSource, TakeBytes};
+pub use target::{PutBytes, WriteTarget};
+pub use writer::{serialized_size, to_vec, to_writer, Writer};
 
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
@@ -0,0 +1,524 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+use crate::error::Result;
+use crate::reader::Reader;
+use crate::source::{ReadSource, TakeBytes};
+use crate::target::{PutBytes, SizeTarget, WriteTarget};
+use crate::writer::Writer;
+
+/// Byte order of multi-byte values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Endian {
+    /// Big endian (network byte order). This is the default.
+    Big,
+
+    /// Little endian.
+    Little,
+}
+
+/// Encoding of integers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Int {
+    /// All integers are encoded with a fixed size. This is the default.
+    Fixed,
+
+    /// Lengths (of strings, byte arrays, seqs and maps) and variant indices
+    /// are encoded as varints. All other integers are encoded with a fixed
+    /// size.
+    VarintPrefixes,
+
+    /// Lengths, variant indices and all integers (except `i8` and `u8`) are
+    /// encoded as varints. Signed integers are zigzag encoded.
+    Varint,
+}
+
+/// Encoding of the fields of a struct (or struct variant).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fields {
+    /// The fields are encoded one after the other in the order in which they
+    /// were defined. This is the default.
+    Positional,
+
+    /// The number of fields is encoded followed by (name, value) pairs.
+    Names,
+
+    /// The number of fields is encoded followed by (index, value) pairs.
+    Indices,
+}
+
+/// Values, which are prefixed with their encoded length (an envelope).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Envelope {
+    /// No envelopes are encoded. This is the default.
+    None,
+
+    /// Every struct is wrapped into an envelope.
+    Structs,
+
+    /// Every struct and every payload of an enum variant is wrapped into an
+    /// envelope.
+    All,
+}
+
+/// Width of an integer prefix (e.g. the length of a string).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Width {
+    /// The prefix is encoded as an `u8` value.
+    U8,
+
+    /// The prefix is encoded as an `u16` value.
+    U16,
+
+    /// The prefix is encoded as an `u32` value.
+    U32,
+
+    /// The prefix is encoded as an `u64` value.
+    U64,
+}
+
+impl Width {
+    /// Returns the number of bits of the prefix.
+    pub(crate) fn bits(&self) -> u32 {
+        match self {
+            Width::U8 => u8::BITS,
+            Width::U16 => u16::BITS,
+            Width::U32 => u32::BITS,
+            Width::U64 => u64::BITS,
+        }
+    }
+
+    /// Returns the greatest value, which can be encoded with the prefix.
+    pub(crate) fn max(&self) -> u64 {
+        match self {
+            Width::U8 => u8::MAX as u64,
+            Width::U16 => u16::MAX as u64,
+            Width::U32 => u32::MAX as u64,
+            Width::U64 => u64::MAX,
+        }
+    }
+}
+
+/// Options to customize the binary format.
+///
+/// Use the `with_*` functions to modify the options and finally create a
+/// [`Reader`] with [`Options::build_reader`] or a [`Writer`] with
+/// [`Options::build_writer`]. Note that the same options must be used for
+/// serialization and deserialization.
+///
+/// ```rust
+/// use nuts_bytes::Options;
+///
+/// let mut writer = Options::new().with_little_endian().build_writer(vec![]);
+/// writer.serialize(&666u32).unwrap();
+/// assert_eq!(writer.into_target(), [0x9A, 0x02, 0x00, 0x00]);
+///
+/// let mut reader = Options::new()
+///     .with_little_endian()
+///     .build_reader([0x9A, 0x02, 0x00, 0x00].as_slice());
+/// assert_eq!(reader.deserialize::<u32>().unwrap(), 666);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    pub(crate) endian: Endian,
+    pub(crate) int: Int,
+    pub(crate) len_width: Width,
+    pub(crate) tag_width: Width,
+    pub(crate) variant_names: bool,
+    pub(crate) fields: Fields,
+    pub(crate) envelope: Envelope,
+    pub(crate) item_limit: Option<usize>,
+    pub(crate) total_limit: Option<usize>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) strict: bool,
+    pub(crate) self_describing: bool,
+}
+
+impl Options {
+    /// Creates the default options.
+    ///
+    /// * Multi-byte values are encoded in big endian.
+    /// * Integers are encoded with a fixed size.
+    /// * Lengths are encoded as `u64` values.
+    /// * Variants are encoded by their index as `u32` values.
+    /// * The fields of a struct are encoded one after the other.
+    /// * No envelopes are encoded.
+    /// * The reader has no limits.
+    /// * The nesting depth is not limited.
+    /// * Deserialization is lenient.
+    /// * The compact (not self-describing) format is used.
+    pub fn new() -> Options {
+        Options {
+            endian: Endian::Big,
+            int: Int::Fixed,
+            len_width: Width::U64,
+            tag_width: Width::U32,
+            variant_names: false,
+            fields: Fields::Positional,
+            envelope: Envelope::None,
+            item_limit: None,
+            total_limit: None,
+            max_depth: None,
+            strict: false,
+            self_describing: false,
+        }
+    }
+
+    /// Sets the byte order of multi-byte values.
+    pub fn with_endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// Encodes multi-byte values in big endian.
+    pub fn with_big_endian(self) -> Self {
+        self.with_endian(Endian::Big)
+    }
+
+    /// Encodes multi-byte values in little endian.
+    pub fn with_little_endian(self) -> Self {
+        self.with_endian(Endian::Little)
+    }
+
+    /// Sets the encoding of integers.
+    pub fn with_int(mut self, int: Int) -> Self {
+        self.int = int;
+        self
+    }
+
+    /// Encodes all integers with a fixed size.
+    pub fn with_fixint(self) -> Self {
+        self.with_int(Int::Fixed)
+    }
+
+    /// Encodes lengths and variant indices as varints.
+    ///
+    /// All other integers are still encoded with a fixed size.
+    pub fn with_varint_prefixes(self) -> Self {
+        self.with_int(Int::VarintPrefixes)
+    }
+
+    /// Encodes lengths, variant indices and all integers as varints.
+    pub fn with_varint(self) -> Self {
+        self.with_int(Int::Varint)
+    }
+
+    /// Sets the width of the length prefix of strings, byte arrays, seqs and
+    /// maps.
+    ///
+    /// Serialization fails with an [`Error::LengthOverflow`] error, if a
+    /// length does not fit into the prefix.
+    ///
+    /// [`Error::LengthOverflow`]: crate::Error::LengthOverflow
+    pub fn with_length_width(mut self, width: Width) -> Self {
+        self.len_width = width;
+        self
+    }
+
+    /// Sets the width of the index of an enum variant.
+    ///
+    /// Serialization fails with an [`Error::TagOverflow`] error, if a variant
+    /// index does not fit into the tag. A variant index is always an `u32`
+    /// value, thus decoding a [`Width::U64`] tag fails with an
+    /// [`Error::InvalidTag`] error, if the tag is greater than `u32::MAX`.
+    ///
+    /// [`Error::TagOverflow`]: crate::Error::TagOverflow
+    /// [`Error::InvalidTag`]: crate::Error::InvalidTag
+    pub fn with_tag_width(mut self, width: Width) -> Self {
+        self.tag_width = width;
+        self
+    }
+
+    /// Encodes an enum variant by its name.
+    ///
+    /// The name of the variant is encoded as a length-prefixed string. Enum
+    /// variants can be reordered or inserted without breaking existing data,
+    /// but renaming a variant does.
+    ///
+    /// Both the writer and the reader must use the same mode.
+    pub fn with_variant_names(mut self) -> Self {
+        self.variant_names = true;
+        self
+    }
+
+    /// Encodes an enum variant by its index (the default).
+    ///
+    /// The width of the index is selected with [`Options::with_tag_width`].
+    pub fn with_variant_indices(mut self) -> Self {
+        self.variant_names = false;
+        self
+    }
+
+    /// Sets the encoding of the fields of a struct.
+    ///
+    /// Both the writer and the reader must use the same mode.
+    pub fn with_fields(mut self, fields: Fields) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Encodes the fields of a struct one after the other (the default).
+    ///
+    /// Adding, removing or reordering a field breaks existing data.
+    pub fn with_positional_fields(self) -> Self {
+        self.with_fields(Fields::Positional)
+    }
+
+    /// Encodes the fields of a struct as (name, value) pairs.
+    ///
+    /// Every value is prefixed with its length, thus unknown fields can be
+    /// skipped and missing fields are filled in with their default value
+    /// (`#[serde(default)]`).
+    ///
+    /// Both the writer and the reader must use the same mode.
+    pub fn with_field_names(self) -> Self {
+        self.with_fields(Fields::Names)
+    }
+
+    /// Encodes the fields of a struct as (index, value) pairs.
+    ///
+    /// Like [`Options::with_field_names`] but a field is identified by its
+    /// index, which is more compact. New fields can be appended to the end of
+    /// the struct without breaking existing data.
+    ///
+    /// In self-describing mode the fields are always encoded with their
+    /// names, because serde identifies the tag of an internally tagged enum
+    /// by its name.
+    pub fn with_field_indices(self) -> Self {
+        self.with_fields(Fields::Indices)
+    }
+
+    /// Selects the values, which are wrapped into an envelope.
+    ///
+    /// An envelope prefixes a value with its encoded length in bytes. A
+    /// reader skips the bytes of an envelope, which were not consumed by the
+    /// value. This way a struct can be extended by fields appended to its end
+    /// and still be read by an older reader.
+    ///
+    /// Envelopes are not encoded in self-describing mode, where the reader is
+    /// able to skip values anyway.
+    ///
+    /// Both the writer and the reader must use the same mode.
+    pub fn with_envelope(mut self, envelope: Envelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// Enables the self-describing mode.
+    ///
+    /// Every value is preceded by a type tag, which allows deserialization
+    /// with [`Deserializer::deserialize_any`]. This is required for targets
+    /// like untagged or internally tagged enums, `#[serde(flatten)]` or
+    /// dynamic value types. The format is described in the
+    /// [self-describing profile] of the format specification.
+    ///
+    /// Both the writer and the reader must use the same mode.
+    ///
+    /// [`Deserializer::deserialize_any`]: serde::Deserializer::deserialize_any
+    /// [self-describing profile]: crate::doc_format#self-describing-profile
+    pub fn with_self_describing(mut self) -> Self {
+        self.self_describing = true;
+        self
+    }
+
+    /// Enables the compact mode (the default).
+    ///
+    /// No type information are encoded, see the [format specification].
+    ///
+    /// [format specification]: crate::doc_format
+    pub fn with_compact(mut self) -> Self {
+        self.self_describing = false;
+        self
+    }
+
+    /// Enables the strict (canonical) mode, when reading.
+    ///
+    /// In strict mode a `bool` and the tag of an option accept only the
+    /// values `0` and `1`. Any other value is rejected with an
+    /// [`Error::InvalidBool`] or [`Error::InvalidOptionTag`] error. This
+    /// ensures that every value has exactly one binary representation.
+    ///
+    /// [`Error::InvalidBool`]: crate::Error::InvalidBool
+    /// [`Error::InvalidOptionTag`]: crate::Error::InvalidOptionTag
+    pub fn with_strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Enables the lenient mode, when reading (the default).
+    ///
+    /// Any non-zero value is taken as `true` for a `bool` and as [`Some`] for
+    /// an option.
+    pub fn with_lenient(mut self) -> Self {
+        self.strict = false;
+        self
+    }
+
+    /// Limits the length of a single item, when reading.
+    ///
+    /// The limit applies to the number of bytes of a string, byte array,
+    /// length-prefixed field value or envelope and to the number of elements
+    /// of a seq or map. The length is checked right after its prefix was
+    /// read, thus before anything is allocated. Deserialization fails with an
+    /// [`Error::LimitExceeded`] error, if the limit is exceeded.
+    ///
+    /// [`Error::LimitExceeded`]: crate::Error::LimitExceeded
+    pub fn with_item_limit(mut self, limit: usize) -> Self {
+        self.item_limit = Some(limit);
+        self
+    }
+
+    /// Limits the total number of bytes a reader consumes from its source.
+    ///
+    /// Before bytes are taken from the source, the reader checks whether
+    /// they fit into the remaining budget. Deserialization fails with an
+    /// [`Error::LimitExceeded`] error, if the budget is exceeded.
+    ///
+    /// [`Error::LimitExceeded`]: crate::Error::LimitExceeded
+    pub fn with_total_limit(mut self, limit: usize) -> Self {
+        self.total_limit = Some(limit);
+        self
+    }
+
+    /// Limits the nesting depth of values.
+    ///
+    /// The content of every option (`Some`), newtype, seq, tuple, map, struct
+    /// and enum variant is one level deeper than its enclosing value.
+    /// Serialization and deserialization fail with an [`Error::DepthExceeded`]
+    /// error, if the depth exceeds `depth`.
+    ///
+    /// [`Error::DepthExceeded`]: crate::Error::DepthExceeded
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Calculates the number of bytes required to serialize the given `value`
+    /// with these options.
+    ///
+    /// See [`serialized_size`](crate::serialized_size) for more information.
+    pub fn serialized_size<T: Serialize>(self, value: &T) -> Result<usize> {
+        let mut writer = self.build_writer(SizeTarget::default());
+
+        writer.serialize(value)?;
+
+        Ok(writer.into_target().size())
+    }
+
+    /// Serializes the given `value` into a [`Vec`] with these options.
+    ///
+    /// See [`to_vec`](crate::to_vec) for more information.
+    pub fn to_vec<T: Serialize>(self, value: &T) -> Result<Vec<u8>> {
+        let mut writer = self.build_writer(vec![]);
+
+        writer.serialize(value).map(|_| writer.into_target())
+    }
+
+    /// Serializes the given `value` into the given [`io::Write`] instance
+    /// with these options.
+    ///
+    /// See [`to_writer`](crate::to_writer) for more information.
+    ///
+    /// [`io::Write`]: std::io::Write
+    pub fn to_writer<W: Write, T: Serialize>(self, writer: W, value: &T) -> Result<usize> {
+        self.build_writer(WriteTarget::new(writer)).serialize(value)
+    }
+
+    /// Deserializes an instance of `T` from the given `bytes` with these
+    /// options.
+    ///
+    /// See [`from_slice`](crate::from_slice) for more information.
+    pub fn from_slice<'de, T: Deserialize<'de>>(self, bytes: &'de [u8]) -> Result<T> {
+        let mut reader = self.build_reader(bytes);
+        let value = reader.deserialize()?;
+
+        reader.finish().map(|()| value)
+    }
+
+    /// Deserializes an instance of `T` from the given `bytes` with these
+    /// options, trailing bytes are returned.
+    ///
+    /// See [`take_from_slice`](crate::take_from_slice) for more information.
+    pub fn take_from_slice<'de, T: Deserialize<'de>>(
+        self,
+        bytes: &'de [u8],
+    ) -> Result<(T, &'de [u8])> {
+        let mut reader = self.build_reader(bytes);
+
+        reader.deserialize().map(|value| (value, *reader.as_ref()))
+    }
+
+    /// Deserializes an instance of `T` from the given [`io::Read`] instance
+    /// with these options.
+    ///
+    /// See [`from_reader`](crate::from_reader) for more information.
+    ///
+    /// [`io::Read`]: std::io::Read
+    pub fn from_reader<R: Read, T: DeserializeOwned>(self, reader: R) -> Result<T> {
+        self.build_reader(ReadSource::new(reader)).deserialize()
+    }
+
+    /// Creates a [`Reader`] with these options, which reads from the given
+    /// `source`.
+    pub fn build_reader<'tb, T: TakeBytes<'tb>>(self, source: T) -> Reader<T> {
+        Reader::with_options(source, self)
+    }
+
+    /// Creates a [`Writer`] with these options, which writes into the given
+    /// `target`.
+    pub fn build_writer<T: PutBytes>(self, target: T) -> Writer<T> {
+        Writer::with_options(target, self)
+    }
+
+    /// Returns the encoding of the fields of a struct.
+    ///
+    /// In self-describing mode a struct is always encoded like a map with
+    /// the names of the fields.
+    pub(crate) fn field_keys(&self) -> Fields {
+        if self.self_describing {
+            Fields::Names
+        } else {
+            self.fields
+        }
+    }
+
+    /// Tests whether a struct is wrapped into an envelope.
+    pub(crate) fn struct_envelope(&self) -> bool {
+        !self.self_describing && self.envelope != Envelope::None
+    }
+
+    /// Tests whether the payload of an enum variant is wrapped into an
+    /// envelope.
+    pub(crate) fn enum_envelope(&self) -> bool {
+        !self.self_describing && self.envelope == Envelope::All
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options::new()
+    }
+}
@@ -23,22 +23,85 @@
 #[cfg(test)]
 mod tests;
 
-use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::de::value::{StrDeserializer, U32Deserializer};
+use serde::de::DeserializeOwned;
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
 use serde::Deserialize;
 use std::borrow::Cow;
-use std::str;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::{fmt, result, str};
 
-use crate::error::{Error, Result};
+use crate::error::{Error, PathSegment, Result};
+use crate::options::{Endian, Fields, Int, Options, Width};
 use crate::source::TakeBytes;
+use crate::tag::TypeTag;
+
+macro_rules! read_fixed {
+    ($reader:ident, $ty:ty) => {{
+        let mut bytes = [0; std::mem::size_of::<$ty>()];
+        $reader
+            .take_bytes_to(&mut bytes)
+            .map(|()| match $reader.options.endian {
+                Endian::Big => <$ty>::from_be_bytes(bytes),
+                Endian::Little => <$ty>::from_le_bytes(bytes),
+            })
+    }};
+}
 
 macro_rules! read_primitive {
     ($(#[$outer:meta])* $name:ident -> $ty:ty) => {
         $(#[$outer])*
         pub fn $name(&mut self) -> Result<$ty> {
-            let mut bytes = [0; std::mem::size_of::<$ty>()];
-            self.source
-                .take_bytes_to(&mut bytes)
-                .map(|()| <$ty>::from_be_bytes(bytes))
+            let result = read_fixed!(self, $ty);
+            self.locate(result)
+        }
+    };
+}
+
+macro_rules! read_integer {
+    ($(#[$outer:meta])* $name:ident -> $ty:ty) => {
+        $(#[$outer])*
+        pub fn $name(&mut self) -> Result<$ty> {
+            let result = match self.options.int {
+                Int::Varint => self.read_varint(<$ty>::BITS).map(|n| n as $ty),
+                Int::Fixed | Int::VarintPrefixes => read_fixed!(self, $ty),
+            };
+            self.locate(result)
+        }
+    };
+
+    ($(#[$outer:meta])* $name:ident -> $ty:ty, zigzag $uty:ty) => {
+        $(#[$outer])*
+        pub fn $name(&mut self) -> Result<$ty> {
+            let result = match self.options.int {
+                Int::Varint => self.read_varint(<$uty>::BITS).map(|n| {
+                    let n = n as $uty;
+                    (n >> 1) as $ty ^ -((n & 1) as $ty)
+                }),
+                Int::Fixed | Int::VarintPrefixes => read_fixed!(self, $ty),
+            };
+            self.locate(result)
+        }
+    };
+}
+
+/// Converts a decoded length into the integer type `U` (usually `usize`).
+///
+/// On targets where `usize` is smaller than 64 bit a large length would be
+/// truncated, thus it is rejected with an [`Error::LengthTooLarge`] error.
+fn checked_len<U: TryFrom<u64>>(len: u64) -> Result<U> {
+    U::try_from(len).map_err(|_| Error::LengthTooLarge(len))
+}
+
+/// In self-describing mode forwards to [`de::Deserializer::deserialize_any`].
+macro_rules! forward_self_describing {
+    ($reader:ident, $visitor:ident) => {
+        if $reader.options.self_describing {
+            return de::Deserializer::deserialize_any($reader, $visitor);
         }
     };
 }
@@ -49,6 +112,9 @@ macro_rules! read_primitive {
 /// binary data from it.
 pub struct Reader<T> {
     source: T,
+    options: Options,
+    position: usize,
+    depth: usize,
 }
 
 impl<'tb, T: TakeBytes<'tb>> Reader<T> {
@@ -56,8 +122,20 @@ impl<'tb, T: TakeBytes<'tb>> Reader<T> {
     ///
     /// The source of the reader is passed to the function. Every type that
     /// implements the [`TakeBytes`] trait can be the source of this reader.
+    ///
+    /// The reader uses the default [`Options`]. Use [`Options::build_reader`]
+    /// to create a reader with custom options.
     pub fn new(source: T) -> Reader<T> {
-        Reader { source }
+        Self::with_options(source, Options::new())
+    }
+
+    pub(crate) fn with_options(source: T, options: Options) -> Reader<T> {
+        Reader {
+            source,
+            options,
+            position: 0,
+            depth: 0,
+        }
     }
 
     /// Deserializes from this binary representation into a data structure
@@ -68,8 +146,39 @@ impl<'tb, T: TakeBytes<'tb>> Reader<T> {
     /// ```text
     /// D::deserialize(self)
     /// ```
+    ///
+    /// Errors are located, they carry the [position](Self::position) of the
+    /// reader, where the error occured (see [`Error::Context`]).
     pub fn deserialize<D: Deserialize<'tb>>(&mut self) -> Result<D> {
-        D::deserialize(self)
+        let result = D::deserialize(&mut *self);
+        self.locate(result)
+    }
+
+    /// Finishes the deserialization.
+    ///
+    /// Verifies that the source is exhausted. Use this function if a
+    /// complete message was deserialized, where trailing data indicates a
+    /// corruption.
+    ///
+    /// # Errors
+    ///
+    /// If the source is not exhausted, an [`Error::TrailingBytes`] error is
+    /// returned. See [`TakeBytes::remaining`] for the number of bytes
+    /// reported by the error.
+    pub fn finish(mut self) -> Result<()> {
+        let result = match self.source.remaining() {
+            Ok(0) => Ok(()),
+            Ok(n) => Err(Error::TrailingBytes(n)),
+            Err(err) => Err(err),
+        };
+        self.locate(result)
+    }
+
+    /// Returns the position of the reader.
+    ///
+    /// This is the number of bytes consumed from the source so far.
+    pub fn position(&self) -> usize {
+        self.position
     }
 
     read_primitive!(
@@ -82,46 +191,272 @@ impl<'tb, T: TakeBytes<'tb>> Reader<T> {
         read_u8 -> u8
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `i16` value from the reader.
-        read_i16 -> i16
+        read_i16 -> i16, zigzag u16
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `u16` value from the reader.
         read_u16 -> u16
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `i32` value from the reader.
-        read_i32 -> i32
+        read_i32 -> i32, zigzag u32
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `u32` value from the reader.
         read_u32 -> u32
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `i64` value from the reader.
-        read_i64 -> i64
+        read_i64 -> i64, zigzag u64
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `u64` value from the reader.
         read_u64 -> u64
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `i128` value from the reader.
-        read_i128 -> i128
+        read_i128 -> i128, zigzag u128
     );
 
-    read_primitive!(
+    read_integer!(
         /// Reads an `u128` value from the reader.
         read_u128 -> u128
     );
 
+    read_primitive!(
+        /// Reads an `f32` value from the reader.
+        read_f32 -> f32
+    );
+
+    read_primitive!(
+        /// Reads an `f64` value from the reader.
+        read_f64 -> f64
+    );
+
+    /// Reads a varint encoded integer, which must fit into `bits` bits.
+    fn read_varint(&mut self, bits: u32) -> Result<u128> {
+        let mut value = 0u128;
+        let mut shift = 0;
+
+        loop {
+            let mut byte = [0; 1];
+            self.take_bytes_to(&mut byte)?;
+
+            let group = (byte[0] & 0x7F) as u128;
+
+            if shift >= bits || (bits - shift < 7 && group >> (bits - shift) != 0) {
+                return Err(Error::InvalidVarint);
+            }
+
+            value |= group << shift;
+
+            if byte[0] & 0x80 == 0 {
+                return if byte[0] == 0 && shift > 0 {
+                    Err(Error::InvalidVarint) // overlong encoding
+                } else {
+                    Ok(value)
+                };
+            }
+
+            shift += 7;
+        }
+    }
+
+    /// Reads an integer prefix of the given `width`.
+    fn read_prefix(&mut self, width: Width) -> Result<u64> {
+        match self.options.int {
+            Int::Fixed => match width {
+                Width::U8 => read_fixed!(self, u8).map(u64::from),
+                Width::U16 => read_fixed!(self, u16).map(u64::from),
+                Width::U32 => read_fixed!(self, u32).map(u64::from),
+                Width::U64 => read_fixed!(self, u64),
+            },
+            Int::VarintPrefixes | Int::Varint => self.read_varint(width.bits()).map(|n| n as u64),
+        }
+    }
+
+    /// Reads the length of a string, byte array, seq or map.
+    ///
+    /// The length is checked against the configured item limit.
+    fn read_len(&mut self) -> Result<usize> {
+        let len = self.read_prefix(self.options.len_width)?;
+
+        match self.options.item_limit {
+            Some(limit) if len > limit as u64 => Err(Error::LimitExceeded(len)),
+            _ => checked_len(len),
+        }
+    }
+
+    /// Reads the index of an enum variant.
+    fn read_tag(&mut self) -> Result<u32> {
+        let tag = self.read_prefix(self.options.tag_width)?;
+
+        u32::try_from(tag).map_err(|_| Error::InvalidTag(tag))
+    }
+
+    /// Reads a `bool` value.
+    fn read_bool(&mut self) -> Result<bool> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            n if self.options.strict => Err(Error::InvalidBool(n)),
+            _ => Ok(true),
+        }
+    }
+
+    /// Reads the type tag of the next value (self-describing mode).
+    fn read_type(&mut self) -> Result<TypeTag> {
+        let n = self.read_u8()?;
+        TypeTag::from_u8(n).ok_or(Error::InvalidType(n))
+    }
+
+    /// Reads the name or index of a field or enum variant (self-describing
+    /// mode).
+    fn read_typed_key(&mut self) -> Result<Key<'tb>> {
+        match self.read_type()? {
+            TypeTag::Str => self.read_str().map(Key::Name),
+            TypeTag::U64 => {
+                let index = self.read_u64()?;
+                let index = u32::try_from(index).map_err(|_| -> Error {
+                    de::Error::invalid_value(Unexpected::Unsigned(index), &"an u32 index")
+                })?;
+
+                Ok(Key::Index(index))
+            }
+            tag => Err(de::Error::invalid_type(
+                Unexpected::Other(&format!("type tag {}", tag as u8)),
+                &"a name or an index",
+            )),
+        }
+    }
+
+    /// Reads a length-prefixed string.
+    fn read_str(&mut self) -> Result<Cow<'tb, str>> {
+        let len = self.read_len()?;
+
+        match self.read_bytes(len)? {
+            Cow::Borrowed(bytes) => str::from_utf8(bytes)
+                .map(Cow::Borrowed)
+                .map_err(Error::InvalidString),
+            Cow::Owned(bytes) => String::from_utf8(bytes)
+                .map(Cow::Owned)
+                .map_err(|err| Error::InvalidString(err.utf8_error())),
+        }
+    }
+
+    /// Reads a length-prefixed string and passes it to the `visitor`.
+    fn visit_str<'de, V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        'tb: 'de,
+    {
+        match self.read_str()? {
+            Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Cow::Owned(s) => visitor.visit_string(s),
+        }
+    }
+
+    /// Reads a length-prefixed byte array and passes it to the `visitor`.
+    fn visit_bytes<'de, V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        'tb: 'de,
+    {
+        let len = self.read_len()?;
+
+        match self.read_bytes(len)? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
+    }
+
+    /// Reads the value described by the type `tag` and passes it to the
+    /// `visitor` (self-describing mode).
+    fn visit_type<'de, V: Visitor<'de>>(&mut self, tag: TypeTag, visitor: V) -> Result<V::Value>
+    where
+        'tb: 'de,
+    {
+        match tag {
+            TypeTag::Unit => visitor.visit_unit(),
+            TypeTag::Bool => visitor.visit_bool(self.read_bool()?),
+            TypeTag::I8 => visitor.visit_i8(self.read_i8()?),
+            TypeTag::I16 => visitor.visit_i16(self.read_i16()?),
+            TypeTag::I32 => visitor.visit_i32(self.read_i32()?),
+            TypeTag::I64 => visitor.visit_i64(self.read_i64()?),
+            TypeTag::I128 => visitor.visit_i128(self.read_i128()?),
+            TypeTag::U8 => visitor.visit_u8(self.read_u8()?),
+            TypeTag::U16 => visitor.visit_u16(self.read_u16()?),
+            TypeTag::U32 => visitor.visit_u32(self.read_u32()?),
+            TypeTag::U64 => visitor.visit_u64(self.read_u64()?),
+            TypeTag::U128 => visitor.visit_u128(self.read_u128()?),
+            TypeTag::F32 => visitor.visit_f32(self.read_f32()?),
+            TypeTag::F64 => visitor.visit_f64(self.read_f64()?),
+            TypeTag::Char => {
+                let n = self.read_u32()?;
+
+                match char::from_u32(n) {
+                    Some(c) => visitor.visit_char(c),
+                    None => Err(Error::InvalidChar(n)),
+                }
+            }
+            TypeTag::Str => self.visit_str(visitor),
+            TypeTag::Bytes => self.visit_bytes(visitor),
+            TypeTag::None => visitor.visit_none(),
+            TypeTag::Some => self.nested(|reader| visitor.visit_some(reader)),
+            TypeTag::Seq => {
+                let len = self.read_len()?;
+                self.nested(|reader| visitor.visit_seq(SequenceReader::new(reader, len)))
+            }
+            TypeTag::Map => {
+                let len = self.read_len()?;
+                self.nested(|reader| visitor.visit_map(SequenceReader::new(reader, len)))
+            }
+        }
+    }
+
+    /// Reads the fields of a struct and passes them to the `visitor`.
+    fn visit_struct<'de, V: Visitor<'de>>(
+        &mut self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        'tb: 'de,
+    {
+        if self.options.self_describing {
+            // keep the field names for the path of an error
+            match self.read_type()? {
+                TypeTag::Seq => {
+                    let len = self.read_len()?;
+                    self.nested(|reader| {
+                        visitor.visit_seq(SequenceReader::with_fields(reader, fields, len))
+                    })
+                }
+                TypeTag::Map => {
+                    let len = self.read_len()?;
+                    self.nested(|reader| visitor.visit_map(FieldReader::new(reader, fields, len)))
+                }
+                tag => self.visit_type(tag, visitor),
+            }
+        } else {
+            match self.options.fields {
+                Fields::Positional => self.nested(|reader| {
+                    visitor.visit_seq(SequenceReader::with_fields(reader, fields, fields.len()))
+                }),
+                Fields::Names | Fields::Indices => {
+                    let len = self.read_len()?;
+                    self.nested(|reader| visitor.visit_map(FieldReader::new(reader, fields, len)))
+                }
+            }
+        }
+    }
+
     /// Reads `n` bytes from the reader.
     ///
     /// If possible a slice of borrowed data of the given size (`n`) wrapped
@@ -134,7 +469,8 @@ impl<'tb, T: TakeBytes<'tb>> Reader<T> {
     ///
     /// If not enough data are available an [`Error::Eof`] error is returned.
     pub fn read_bytes(&mut self, n: usize) -> Result<Cow<'tb, [u8]>> {
-        self.source.take_bytes(n)
+        let result = self.take_bytes(n);
+        self.locate(result)
     }
 
     /// Reads some bytes from the reader and puts them into the given buffer
@@ -145,7 +481,110 @@ impl<'tb, T: TakeBytes<'tb>> Reader<T> {
     /// If not enough data are available to fill `buf` an [`Error::Eof`] error
     /// is returned.
     pub fn read_bytes_to(&mut self, buf: &mut [u8]) -> Result<()> {
-        self.source.take_bytes_to(buf)
+        let result = self.take_bytes_to(buf);
+        self.locate(result)
+    }
+
+    /// Takes `n` bytes from the source.
+    ///
+    /// All reads from the source go through this function,
+    /// [`Self::take_bytes_to`] or [`Self::skip_bytes`], which account for the
+    /// consumed bytes.
+    fn take_bytes(&mut self, n: usize) -> Result<Cow<'tb, [u8]>> {
+        self.reserve(n)?;
+        let bytes = self.source.take_bytes(n)?;
+
+        self.position += n;
+        Ok(bytes)
+    }
+
+    /// Takes bytes from the source and puts them into `buf`.
+    ///
+    /// All reads from the source go through this function,
+    /// [`Self::take_bytes`] or [`Self::skip_bytes`], which account for the
+    /// consumed bytes.
+    fn take_bytes_to(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reserve(buf.len())?;
+        self.source
+            .take_bytes_to(buf)
+            .map(|()| self.position += buf.len())
+    }
+
+    /// Skips `n` bytes of the source.
+    ///
+    /// All reads from the source go through this function,
+    /// [`Self::take_bytes`] or [`Self::take_bytes_to`], which account for the
+    /// consumed bytes.
+    fn skip_bytes(&mut self, n: usize) -> Result<()> {
+        self.reserve(n)?;
+        self.source.skip(n).map(|()| self.position += n)
+    }
+
+    /// Reads a value prefixed with its length in bytes.
+    ///
+    /// The value is read by `f`. Bytes of the value, which are not consumed
+    /// by `f`, are skipped. The length is checked against the configured item
+    /// limit. Fails with an [`Error::Overrun`] error, if `f` consumes more
+    /// bytes than the length of the value.
+    fn delimited<R, F: FnOnce(&mut Self) -> Result<R>>(&mut self, f: F) -> Result<R> {
+        let len = self.read_len()?;
+        let end = self.position.saturating_add(len);
+
+        let value = f(self)?;
+
+        match end.checked_sub(self.position) {
+            Some(n) => self.skip_bytes(n).map(|()| value),
+            None => Err(Error::Overrun(len)),
+        }
+    }
+
+    /// Reads a value, which is wrapped into an envelope, if `envelope` is
+    /// set.
+    ///
+    /// See [`Self::delimited`] for more information.
+    fn enveloped<R, F: FnOnce(&mut Self) -> Result<R>>(
+        &mut self,
+        envelope: bool,
+        f: F,
+    ) -> Result<R> {
+        if envelope {
+            self.delimited(f)
+        } else {
+            f(self)
+        }
+    }
+
+    /// Runs `f` one nesting level deeper.
+    ///
+    /// Fails with an [`Error::DepthExceeded`] error, if the configured maximum
+    /// depth is exceeded.
+    fn nested<R, F: FnOnce(&mut Self) -> Result<R>>(&mut self, f: F) -> Result<R> {
+        if let Some(max) = self.options.max_depth {
+            if self.depth >= max {
+                return Err(Error::DepthExceeded(max));
+            }
+        }
+
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Attaches the current position to the error of `result`.
+    fn locate<R>(&self, result: Result<R>) -> Result<R> {
+        result.map_err(|err| err.at(self.position))
+    }
+
+    /// Verifies that another `n` bytes fit into the total limit.
+    fn reserve(&self, n: usize) -> Result<()> {
+        match self.options.total_limit {
+            Some(limit) if n > limit.saturating_sub(self.position) => {
+                Err(Error::LimitExceeded(n as u64))
+            }
+            _ => Ok(()),
+        }
     }
 }
 
@@ -155,67 +594,114 @@ impl<'tb, T: TakeBytes<'tb>> AsRef<T> for Reader<T> {
     }
 }
 
-impl<'a, 'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &'a mut Reader<T> {
+impl<'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &mut Reader<T> {
     type Error = Error;
 
-    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
-        unimplemented!()
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.options.self_describing {
+            let tag = self.read_type()?;
+            self.visit_type(tag, visitor)
+        } else {
+            // The format is not self-describing, the type of the next value
+            // cannot be determined.
+            Err(Error::Unsupported("deserialize_any"))
+        }
     }
 
     fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        let n = self.read_u8()?;
-        visitor.visit_bool(n != 0)
+        forward_self_describing!(self, visitor);
+
+        let b = self.read_bool()?;
+        visitor.visit_bool(b)
     }
 
     fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_i8()?;
         visitor.visit_i8(n)
     }
 
     fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_i16()?;
         visitor.visit_i16(n)
     }
 
     fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_i32()?;
         visitor.visit_i32(n)
     }
 
     fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_i64()?;
         visitor.visit_i64(n)
     }
 
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
+        let n = self.read_i128()?;
+        visitor.visit_i128(n)
+    }
+
     fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_u8()?;
         visitor.visit_u8(n)
     }
 
     fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_u16()?;
         visitor.visit_u16(n)
     }
 
     fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_u32()?;
         visitor.visit_u32(n)
     }
 
     fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_u64()?;
         visitor.visit_u64(n)
     }
 
-    fn deserialize_f32<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
-        unimplemented!()
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
+        let n = self.read_u128()?;
+        visitor.visit_u128(n)
     }
 
-    fn deserialize_f64<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
-        unimplemented!()
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
+        let n = self.read_f32()?;
+        visitor.visit_f32(n)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
+        let n = self.read_f64()?;
+        visitor.visit_f64(n)
     }
 
     fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         let n = self.read_u32()?;
 
         match char::from_u32(n) {
@@ -225,18 +711,9 @@ impl<'a, 'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &'a mut Rea
     }
 
     fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        let len = self.read_u64()? as usize;
+        forward_self_describing!(self, visitor);
 
-        match self.read_bytes(len)? {
-            Cow::Borrowed(bytes) => match str::from_utf8(bytes) {
-                Ok(s) => visitor.visit_borrowed_str(s),
-                Err(err) => Err(Error::InvalidString(err)),
-            },
-            Cow::Owned(bytes) => match String::from_utf8(bytes) {
-                Ok(s) => visitor.visit_string(s),
-                Err(err) => Err(Error::InvalidString(err.utf8_error())),
-            },
-        }
+        self.visit_str(visitor)
     }
 
     fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
@@ -244,12 +721,9 @@ impl<'a, 'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &'a mut Rea
     }
 
     fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        let len = self.read_u64()? as usize;
+        forward_self_describing!(self, visitor);
 
-        match self.read_bytes(len)? {
-            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
-            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
-        }
+        self.visit_bytes(visitor)
     }
 
     fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
@@ -257,16 +731,18 @@ impl<'a, 'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &'a mut Rea
     }
 
     fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        let n = self.read_u8()?;
+        forward_self_describing!(self, visitor);
 
-        if n == 0 {
-            visitor.visit_none()
-        } else {
-            visitor.visit_some(self)
+        match self.read_u8()? {
+            0 => visitor.visit_none(),
+            n if n != 1 && self.options.strict => Err(Error::InvalidOptionTag(n)),
+            _ => self.nested(|reader| visitor.visit_some(reader)),
         }
     }
 
     fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
         visitor.visit_unit()
     }
 
@@ -283,16 +759,20 @@ impl<'a, 'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &'a mut Rea
         _name: &'static str,
         visitor: V,
     ) -> Result<V::Value> {
-        visitor.visit_newtype_struct(self)
+        self.nested(|reader| visitor.visit_newtype_struct(reader))
     }
 
     fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        let len = self.read_u64()? as usize;
-        visitor.visit_seq(SequenceReader::new(self, len))
+        forward_self_describing!(self, visitor);
+
+        let len = self.read_len()?;
+        self.nested(|reader| visitor.visit_seq(SequenceReader::new(reader, len)))
     }
 
     fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
-        visitor.visit_seq(SequenceReader::new(self, len))
+        forward_self_describing!(self, visitor);
+
+        self.nested(|reader| visitor.visit_seq(SequenceReader::new(reader, len)))
     }
 
     fn deserialize_tuple_struct<V: Visitor<'de>>(
@@ -301,12 +781,16 @@ impl<'a, 'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &'a mut Rea
         len: usize,
         visitor: V,
     ) -> Result<V::Value> {
-        visitor.visit_seq(SequenceReader::new(self, len))
+        forward_self_describing!(self, visitor);
+
+        self.nested(|reader| visitor.visit_seq(SequenceReader::new(reader, len)))
     }
 
     fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        let len = self.read_u64()? as usize;
-        visitor.visit_map(SequenceReader::new(self, len))
+        forward_self_describing!(self, visitor);
+
+        let len = self.read_len()?;
+        self.nested(|reader| visitor.visit_map(SequenceReader::new(reader, len)))
     }
 
     fn deserialize_struct<V: Visitor<'de>>(
@@ -315,29 +799,92 @@ impl<'a, 'de, 'tb: 'de, T: TakeBytes<'tb>> de::Deserializer<'de> for &'a mut Rea
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value> {
-        visitor.visit_seq(SequenceReader::new(self, fields.len()))
+        let envelope = self.options.struct_envelope();
+        self.enveloped(envelope, |reader| reader.visit_struct(fields, visitor))
     }
 
     fn deserialize_enum<V: Visitor<'de>>(
         self,
         _name: &'static str,
-        _variants: &'static [&'static str],
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value> {
-        visitor.visit_enum(EnumReader::new(self))
+        if self.options.self_describing {
+            // an enum is encoded as a map with a single entry
+            match self.read_type()? {
+                TypeTag::Map => match self.read_len()? {
+                    1 => visitor.visit_enum(EnumReader::new(self, variants)),
+                    n => Err(de::Error::invalid_length(n, &"a map with a single entry")),
+                },
+                tag => self.visit_type(tag, visitor),
+            }
+        } else {
+            visitor.visit_enum(EnumReader::new(self, variants))
+        }
     }
 
     fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        self.deserialize_u32(visitor)
+        forward_self_describing!(self, visitor);
+
+        if self.options.variant_names {
+            self.visit_str(visitor)
+        } else {
+            let n = self.read_tag()?;
+            visitor.visit_u32(n)
+        }
     }
 
-    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
-        unimplemented!()
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        forward_self_describing!(self, visitor);
+
+        // In compact mode the format is not self-describing, the size of the
+        // ignored value is unknown. Nothing is consumed.
+        visitor.visit_unit()
     }
 }
 
+/// Deserializes an instance of `T` from the given `bytes`.
+///
+/// All bytes must be consumed by the deserialization. If some bytes are left,
+/// an [`Error::TrailingBytes`] error is returned. Use [`take_from_slice`] if
+/// trailing bytes should be accepted.
+///
+/// The default [`Options`] are used. Use [`Options::from_slice`] to customize
+/// the format.
+pub fn from_slice<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T> {
+    Options::new().from_slice(bytes)
+}
+
+/// Deserializes an instance of `T` from the given `bytes`.
+///
+/// Other than [`from_slice`] the bytes are not required to be consumed
+/// completely. The deserialized value is returned together with the unread
+/// part of `bytes`.
+///
+/// The default [`Options`] are used. Use [`Options::take_from_slice`] to
+/// customize the format.
+pub fn take_from_slice<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<(T, &'de [u8])> {
+    Options::new().take_from_slice(bytes)
+}
+
+/// Deserializes an instance of `T` from the given [`io::Read`] instance.
+///
+/// Only the bytes required for the deserialization are read from `reader`.
+///
+/// The default [`Options`] are used, which do not limit the input. Use
+/// [`Options::from_reader`] with [`Options::with_item_limit`],
+/// [`Options::with_total_limit`] and [`Options::with_max_depth`] to read
+/// untrusted input.
+///
+/// [`io::Read`]: std::io::Read
+pub fn from_reader<R: Read, T: DeserializeOwned>(reader: R) -> Result<T> {
+    Options::new().from_reader(reader)
+}
+
 struct SequenceReader<'a, T> {
     reader: &'a mut Reader<T>,
+    fields: Option<&'static [&'static str]>,
+    key: Option<String>,
     cur: usize,
     len: usize,
 }
@@ -346,10 +893,41 @@ impl<'a, T> SequenceReader<'a, T> {
     fn new(reader: &'a mut Reader<T>, len: usize) -> Self {
         SequenceReader {
             reader,
+            fields: None,
+            key: None,
             cur: 0,
             len,
         }
     }
+
+    fn with_fields(reader: &'a mut Reader<T>, fields: &'static [&'static str], len: usize) -> Self {
+        SequenceReader {
+            fields: Some(fields),
+            ..Self::new(reader, len)
+        }
+    }
+
+    /// Adds the path segment of the current element to `err`.
+    fn in_path(&self, err: Error) -> Error {
+        let segment = match self.fields.and_then(|fields| fields.get(self.cur)) {
+            Some(name) => PathSegment::Field(name),
+            None => PathSegment::Index(self.cur),
+        };
+
+        err.in_path(self.reader.position, segment)
+    }
+
+    /// Adds the path segment of the current map entry to `err`.
+    ///
+    /// The entry is identified by its key, if it is a string or an integer.
+    fn in_entry_path(&mut self, err: Error) -> Error {
+        let segment = match self.key.take() {
+            Some(key) => PathSegment::Key(key),
+            None => PathSegment::Entry(self.cur),
+        };
+
+        err.in_path(self.reader.position, segment)
+    }
 }
 
 impl<'a, 'de, 'tb: 'de, B: TakeBytes<'tb>> SeqAccess<'de> for SequenceReader<'a, B> {
@@ -357,10 +935,13 @@ impl<'a, 'de, 'tb: 'de, B: TakeBytes<'tb>> SeqAccess<'de> for SequenceReader<'a,
 
     fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
         if self.cur < self.len {
-            seed.deserialize(&mut *self.reader).map(|value| {
-                self.cur += 1;
-                Some(value)
-            })
+            match seed.deserialize(&mut *self.reader) {
+                Ok(value) => {
+                    self.cur += 1;
+                    Ok(Some(value))
+                }
+                Err(err) => Err(self.in_path(err)),
+            }
         } else {
             Ok(None)
         }
@@ -372,27 +953,348 @@ impl<'a, 'de, 'tb: 'de, B: TakeBytes<'tb>> MapAccess<'de> for SequenceReader<'a,
 
     fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
         if self.cur < self.len {
-            seed.deserialize(&mut *self.reader).map(|value| {
+            let seed = KeySeed {
+                seed,
+                key: &mut self.key,
+            };
+
+            match seed.deserialize(&mut *self.reader) {
+                Ok(value) => Ok(Some(value)),
+                Err(err) => Err(self.in_entry_path(err)),
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        match seed.deserialize(&mut *self.reader) {
+            Ok(value) => {
+                self.key = None;
                 self.cur += 1;
-                Some(value)
-            })
+                Ok(value)
+            }
+            Err(err) => Err(self.in_entry_path(err)),
+        }
+    }
+}
+
+/// A [`DeserializeSeed`], which records a string or integer key of a map
+/// entry for the path of an error.
+struct KeySeed<'k, S> {
+    seed: S,
+    key: &'k mut Option<String>,
+}
+
+impl<'de, 'k, S: DeserializeSeed<'de>> DeserializeSeed<'de> for KeySeed<'k, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> result::Result<S::Value, D::Error> {
+        self.seed.deserialize(KeyDeserializer {
+            inner: deserializer,
+            key: self.key,
+        })
+    }
+}
+
+/// Passes a [`KeyVisitor`] to the wrapped deserializer.
+struct KeyDeserializer<'k, D> {
+    inner: D,
+    key: &'k mut Option<String>,
+}
+
+macro_rules! forward_key {
+    ($($name:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $name<V: Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> result::Result<V::Value, D::Error> {
+                let visitor = KeyVisitor {
+                    inner: visitor,
+                    key: self.key,
+                };
+
+                self.inner.$name($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, 'k, D: de::Deserializer<'de>> de::Deserializer<'de> for KeyDeserializer<'k, D> {
+    type Error = D::Error;
+
+    forward_key!(
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+}
+
+/// Records a string or integer key and passes it to the wrapped visitor.
+///
+/// Compound values are passed on without recording anything.
+struct KeyVisitor<'k, V> {
+    inner: V,
+    key: &'k mut Option<String>,
+}
+
+macro_rules! record_key {
+    ($($name:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $name<E: de::Error>(self, v: $ty) -> result::Result<V::Value, E> {
+                *self.key = Some(v.to_string());
+                self.inner.$name(v)
+            }
+        )*
+    };
+}
+
+macro_rules! forward_visit {
+    ($($name:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $name<E: de::Error>(self, $($arg: $ty),*) -> result::Result<V::Value, E> {
+                self.inner.$name($($arg),*)
+            }
+        )*
+    };
+}
+
+impl<'de, 'k, V: Visitor<'de>> Visitor<'de> for KeyVisitor<'k, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(fmt)
+    }
+
+    record_key!(
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+    );
+
+    forward_visit!(
+        visit_bool(v: bool),
+        visit_f32(v: f32),
+        visit_f64(v: f64),
+        visit_bytes(v: &[u8]),
+        visit_borrowed_bytes(v: &'de [u8]),
+        visit_byte_buf(v: Vec<u8>),
+        visit_none(),
+        visit_unit(),
+    );
+
+    fn visit_some<D: de::Deserializer<'de>>(self, d: D) -> result::Result<V::Value, D::Error> {
+        self.inner.visit_some(d)
+    }
+
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        d: D,
+    ) -> result::Result<V::Value, D::Error> {
+        self.inner.visit_newtype_struct(d)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> result::Result<V::Value, A::Error> {
+        self.inner.visit_seq(seq)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> result::Result<V::Value, A::Error> {
+        self.inner.visit_map(map)
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> result::Result<V::Value, A::Error> {
+        self.inner.visit_enum(data)
+    }
+}
+
+/// The name or index of a field or enum variant.
+enum Key<'tb> {
+    Name(Cow<'tb, str>),
+    Index(u32),
+}
+
+impl<'tb> Key<'tb> {
+    /// Returns the index of the key in `names`.
+    ///
+    /// Returns `None`, if the name is unknown.
+    fn resolve(&self, names: &[&str]) -> Option<usize> {
+        match self {
+            Key::Name(name) => names.iter().position(|n| n == name),
+            Key::Index(index) => Some(*index as usize),
+        }
+    }
+
+    /// Passes the key to the given `seed`.
+    fn deserialize<'de, S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value> {
+        match self {
+            Key::Name(name) => {
+                let name: StrDeserializer<Error> = name.as_ref().into_deserializer();
+                seed.deserialize(name)
+            }
+            Key::Index(index) => {
+                let index: U32Deserializer<Error> = index.into_deserializer();
+                seed.deserialize(index)
+            }
+        }
+    }
+}
+
+/// Reads the (key, value) pairs of a struct with tagged fields.
+struct FieldReader<'a, T> {
+    reader: &'a mut Reader<T>,
+    fields: &'static [&'static str],
+    field: Option<&'static str>,
+    cur: usize,
+    len: usize,
+}
+
+impl<'a, T> FieldReader<'a, T> {
+    fn new(reader: &'a mut Reader<T>, fields: &'static [&'static str], len: usize) -> Self {
+        FieldReader {
+            reader,
+            fields,
+            field: None,
+            cur: 0,
+            len,
+        }
+    }
+
+    /// Adds the path segment of the current field to `err`.
+    ///
+    /// An unknown field is identified by the index of the pair.
+    fn in_path(&self, err: Error) -> Error {
+        let segment = match self.field {
+            Some(name) => PathSegment::Field(name),
+            None => PathSegment::Index(self.cur),
+        };
+
+        err.in_path(self.reader.position, segment)
+    }
+}
+
+impl<'a, 'tb, B: TakeBytes<'tb>> FieldReader<'a, B> {
+    /// Reads the name or index of the next field.
+    fn read_key<'de, K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<K::Value>
+    where
+        'tb: 'de,
+    {
+        self.field = None;
+
+        let key = if self.reader.options.self_describing {
+            self.reader.read_typed_key()?
+        } else if let Fields::Indices = self.reader.options.fields {
+            Key::Index(self.reader.read_u32()?)
+        } else {
+            Key::Name(self.reader.read_str()?)
+        };
+
+        self.field = key
+            .resolve(self.fields)
+            .and_then(|n| self.fields.get(n))
+            .copied();
+
+        key.deserialize(seed)
+    }
+}
+
+impl<'a, 'de, 'tb: 'de, B: TakeBytes<'tb>> MapAccess<'de> for FieldReader<'a, B> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.cur < self.len {
+            self.read_key(seed)
+                .map(Some)
+                .map_err(|err| self.in_path(err))
         } else {
             Ok(None)
         }
     }
 
     fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
-        seed.deserialize(&mut *self.reader)
+        // the reader can skip a value in self-describing mode
+        let delimited = !self.reader.options.self_describing;
+
+        match self
+            .reader
+            .enveloped(delimited, |reader| seed.deserialize(reader))
+        {
+            Ok(value) => {
+                self.cur += 1;
+                Ok(value)
+            }
+            Err(err) => Err(self.in_path(err)),
+        }
     }
 }
 
 struct EnumReader<'a, T> {
     reader: &'a mut Reader<T>,
+    variants: &'static [&'static str],
+    variant: Option<u32>,
 }
 
 impl<'a, T> EnumReader<'a, T> {
-    fn new(reader: &'a mut Reader<T>) -> Self {
-        EnumReader { reader }
+    fn new(reader: &'a mut Reader<T>, variants: &'static [&'static str]) -> Self {
+        EnumReader {
+            reader,
+            variants,
+            variant: None,
+        }
+    }
+
+    /// Adds the path segment of the variant to `err`.
+    ///
+    /// Nothing is added, if the index of the variant is unknown (a variant
+    /// name, which cannot be resolved).
+    fn in_path(&self, err: Error) -> Error {
+        match self.variant {
+            Some(index) => err.in_path(self.reader.position, PathSegment::Variant(index)),
+            None => err,
+        }
     }
 }
 
@@ -400,8 +1302,21 @@ impl<'a, 'de, 'tb: 'de, B: TakeBytes<'tb>> EnumAccess<'de> for EnumReader<'a, B>
     type Error = Error;
     type Variant = Self;
 
-    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
-        let value = seed.deserialize(&mut *self.reader)?;
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        mut self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant)> {
+        let key = if self.reader.options.self_describing {
+            self.reader.read_typed_key()?
+        } else if self.reader.options.variant_names {
+            Key::Name(self.reader.read_str()?)
+        } else {
+            Key::Index(self.reader.read_tag()?)
+        };
+
+        self.variant = key.resolve(self.variants).map(|n| n as u32);
+
+        let value = key.deserialize(seed)?;
         Ok((value, self))
     }
 }
@@ -410,15 +1325,32 @@ impl<'a, 'de, 'tb: 'de, B: TakeBytes<'tb>> VariantAccess<'de> for EnumReader<'a,
     type Error = Error;
 
     fn unit_variant(self) -> Result<()> {
-        Ok(())
+        let envelope = self.reader.options.enum_envelope();
+
+        // consumes the unit type tag in self-describing mode
+        self.reader
+            .enveloped(envelope, |reader| <()>::deserialize(reader))
+            .map_err(|err| self.in_path(err))
     }
 
     fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
-        seed.deserialize(&mut *self.reader)
+        let envelope = self.reader.options.enum_envelope();
+
+        self.reader
+            .enveloped(envelope, |reader| {
+                reader.nested(|reader| seed.deserialize(reader))
+            })
+            .map_err(|err| self.in_path(err))
     }
 
     fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
-        de::Deserializer::deserialize_tuple(self.reader, len, visitor)
+        let envelope = self.reader.options.enum_envelope();
+
+        self.reader
+            .enveloped(envelope, |reader| {
+                de::Deserializer::deserialize_tuple(reader, len, visitor)
+            })
+            .map_err(|err| self.in_path(err))
     }
 
     fn struct_variant<V: Visitor<'de>>(
@@ -426,6 +1358,10 @@ impl<'a, 'de, 'tb: 'de, B: TakeBytes<'tb>> VariantAccess<'de> for EnumReader<'a,
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value> {
-        de::Deserializer::deserialize_struct(self.reader, "", fields, visitor)
+        let envelope = self.reader.options.enum_envelope();
+
+        self.reader
+            .enveloped(envelope, |reader| reader.visit_struct(fields, visitor))
+            .map_err(|err| self.in_path(err))
     }
 }
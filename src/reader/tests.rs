@@ -21,4 +21,67 @@
 // IN THE SOFTWARE.
 
 mod de;
+mod depth;
+mod endian;
+mod envelope;
+mod fields;
+mod func;
+mod len;
+mod limit;
+mod path;
+mod position;
 mod reader;
+mod self_describing;
+mod strict;
+mod variant_names;
+mod varint;
+mod width;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+use crate::options::Options;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct V1 {
+    a: u8,
+    b: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct V2 {
+    a: u8,
+    b: String,
+    #[serde(default)]
+    c: Vec<u16>,
+}
+
+fn v1() -> V1 {
+    V1 {
+        a: 1,
+        b: String::from("abc"),
+    }
+}
+
+fn v2() -> V2 {
+    V2 {
+        a: 1,
+        b: String::from("abc"),
+        c: vec![1, 2],
+    }
+}
+
+fn serialize<T: Serialize>(options: Options, value: &T) -> Vec<u8> {
+    let mut writer = options.build_writer(vec![]);
+    writer.serialize(value).unwrap();
+    writer.into_target()
+}
+
+fn roundtrip<T: Debug + PartialEq + Serialize + DeserializeOwned>(options: Options, value: T) {
+    let buf = serialize(options, &value);
+
+    let mut reader = options.build_reader(buf.as_slice());
+    assert_eq!(reader.deserialize::<T>().unwrap(), value);
+    assert_eq!(reader.as_ref(), &[]);
+}
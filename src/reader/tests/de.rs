@@ -20,6 +20,7 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
 // IN THE SOFTWARE.
 
+use serde::de::IgnoredAny;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -31,11 +32,11 @@ use crate::{assert_error, assert_error_eq};
 fn bool() {
     for buf in [[1], [2]] {
         let mut reader = Reader::new(buf.as_slice());
-        assert_eq!(reader.deserialize::<bool>().unwrap(), true);
+        assert!(reader.deserialize::<bool>().unwrap());
     }
 
     let mut reader = Reader::new([0].as_slice());
-    assert_eq!(reader.deserialize::<bool>().unwrap(), false);
+    assert!(!reader.deserialize::<bool>().unwrap());
 }
 
 #[test]
@@ -120,16 +121,113 @@ fn u64() {
 
 #[test]
 fn i128() {
-    let mut reader = Reader::new([0].as_slice());
-    let err = reader.deserialize::<i128>().unwrap_err();
-    assert_error_eq!(err, Error::Serde(|msg| "i128 is not supported"));
+    for (buf, n) in [
+        (
+            [
+                0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xff,
+            ],
+            -1,
+        ),
+        (
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ],
+            0,
+        ),
+        (
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x01,
+            ],
+            1,
+        ),
+    ] {
+        let mut reader = Reader::new(buf.as_slice());
+        assert_eq!(reader.deserialize::<i128>().unwrap(), n);
+    }
 }
 
 #[test]
 fn u128() {
-    let mut reader = Reader::new([0].as_slice());
-    let err = reader.deserialize::<u128>().unwrap_err();
-    assert_error_eq!(err, Error::Serde(|msg| "u128 is not supported"));
+    for (buf, n) in [
+        (
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ],
+            0,
+        ),
+        (
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x01,
+            ],
+            1,
+        ),
+        (
+            [
+                0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+                0x0F, 0x10,
+            ],
+            0x0102030405060708090A0B0C0D0E0F10,
+        ),
+    ] {
+        let mut reader = Reader::new(buf.as_slice());
+        assert_eq!(reader.deserialize::<u128>().unwrap(), n);
+    }
+}
+
+#[test]
+fn f32() {
+    for (buf, n) in [
+        ([0x3F, 0x80, 0x00, 0x00], 1.0f32),
+        ([0xC0, 0x00, 0x00, 0x00], -2.0),
+        ([0x7F, 0x80, 0x00, 0x00], f32::INFINITY),
+    ] {
+        let mut reader = Reader::new(buf.as_slice());
+        assert_eq!(reader.deserialize::<f32>().unwrap(), n);
+    }
+
+    // signed zero
+    let mut reader = Reader::new([0x80, 0x00, 0x00, 0x00].as_slice());
+    let n = reader.deserialize::<f32>().unwrap();
+    assert_eq!(n, 0.0);
+    assert!(n.is_sign_negative());
+
+    // NaN with payload
+    let mut reader = Reader::new([0x7F, 0xC0, 0x12, 0x34].as_slice());
+    let n = reader.deserialize::<f32>().unwrap();
+    assert!(n.is_nan());
+    assert_eq!(n.to_bits(), 0x7FC01234);
+}
+
+#[test]
+fn f64() {
+    for (buf, n) in [
+        ([0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], 1.0f64),
+        ([0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], -2.0),
+        (
+            [0x7F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            f64::INFINITY,
+        ),
+    ] {
+        let mut reader = Reader::new(buf.as_slice());
+        assert_eq!(reader.deserialize::<f64>().unwrap(), n);
+    }
+
+    // signed zero
+    let mut reader = Reader::new([0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00].as_slice());
+    let n = reader.deserialize::<f64>().unwrap();
+    assert_eq!(n, 0.0);
+    assert!(n.is_sign_negative());
+
+    // NaN with payload
+    let mut reader = Reader::new([0x7F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x12, 0x34].as_slice());
+    let n = reader.deserialize::<f64>().unwrap();
+    assert!(n.is_nan());
+    assert_eq!(n.to_bits(), 0x7FF8000000001234);
 }
 
 #[test]
@@ -139,7 +237,7 @@ fn char() {
 
     let mut reader = Reader::new([0x00, 0x11, 0x00, 0x00].as_slice());
     let err = reader.deserialize::<char>().unwrap_err();
-    assert_error_eq!(err, Error::InvalidChar(|n| 0x110000));
+    assert_error_eq!(err.into_inner(), Error::InvalidChar(|n| 0x110000));
 }
 
 #[test]
@@ -173,7 +271,7 @@ fn str() {
     );
     let err = reader.deserialize::<&str>().unwrap_err();
     assert_error!(
-        err,
+        err.into_inner(),
         Error::InvalidString(
             |cause| cause.to_string() == "invalid utf-8 sequence of 1 bytes from index 1"
         )
@@ -211,7 +309,7 @@ fn string() {
     );
     let err = reader.deserialize::<String>().unwrap_err();
     assert_error!(
-        err,
+        err.into_inner(),
         Error::InvalidString(
             |cause| cause.to_string() == "invalid utf-8 sequence of 1 bytes from index 1"
         )
@@ -412,7 +510,53 @@ fn r#enum() {
     let mut reader = Reader::new([0x00, 0x00, 0x00, 0x04].as_slice());
     let err = reader.deserialize::<Enum>().unwrap_err();
     assert_error_eq!(
-        err,
+        err.into_inner(),
         Error::Serde(|msg| "invalid value: integer `4`, expected variant index 0 <= i < 4")
     );
 }
+
+#[test]
+fn ignored_any() {
+    let mut reader = Reader::new([1, 2].as_slice());
+    assert_eq!(reader.deserialize::<IgnoredAny>().unwrap(), IgnoredAny);
+    assert_eq!(reader.as_ref(), &[1, 2]);
+
+    let mut reader = Reader::new([1, 2].as_slice());
+    assert_eq!(
+        reader.deserialize::<(u8, IgnoredAny, u8)>().unwrap(),
+        (1, IgnoredAny, 2)
+    );
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn any() {
+    #[derive(Debug, Deserialize)]
+    #[serde(untagged)]
+    enum Untagged {
+        #[allow(dead_code)]
+        V1(u16),
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(tag = "type")]
+    enum InternallyTagged {
+        V1,
+    }
+
+    let mut reader = Reader::new([0x00, 0x01].as_slice());
+    let err = reader.deserialize::<Untagged>().unwrap_err();
+    assert_error_eq!(
+        err.into_inner(),
+        Error::Unsupported(|what| "deserialize_any")
+    );
+    assert_eq!(reader.as_ref(), &[0x00, 0x01]);
+
+    let mut reader = Reader::new([0x00, 0x01].as_slice());
+    let err = reader.deserialize::<InternallyTagged>().unwrap_err();
+    assert_error_eq!(
+        err.into_inner(),
+        Error::Unsupported(|what| "deserialize_any")
+    );
+    assert_eq!(reader.as_ref(), &[0x00, 0x01]);
+}
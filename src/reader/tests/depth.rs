@@ -0,0 +1,135 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Deserialize;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::Options;
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Node {
+    Leaf,
+    Next(Box<Node>),
+}
+
+fn nodes(n: usize) -> Vec<u8> {
+    let mut buf = [0, 0, 0, 1].repeat(n);
+    buf.extend_from_slice(&[0, 0, 0, 0]);
+    buf
+}
+
+#[test]
+fn unlimited() {
+    let buf = nodes(2);
+    let mut reader = Options::new().build_reader(buf.as_slice());
+    assert_eq!(
+        reader.deserialize::<Node>().unwrap(),
+        Node::Next(Box::new(Node::Next(Box::new(Node::Leaf))))
+    );
+}
+
+#[test]
+fn enum_in_limit() {
+    let buf = nodes(3);
+    let mut reader = Options::new()
+        .with_max_depth(3)
+        .build_reader(buf.as_slice());
+    reader.deserialize::<Node>().unwrap();
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn enum_exceeded() {
+    let buf = nodes(4);
+    let mut reader = Options::new()
+        .with_max_depth(3)
+        .build_reader(buf.as_slice());
+    let err = reader.deserialize::<Node>().unwrap_err();
+    assert_error!(err.into_inner(), Error::DepthExceeded(|n| n == 3));
+}
+
+#[test]
+fn enum_pathological() {
+    let buf = nodes(1_000_000);
+    let mut reader = Options::new()
+        .with_max_depth(128)
+        .build_reader(buf.as_slice());
+    let err = reader.deserialize::<Node>().unwrap_err();
+    assert_error!(err.into_inner(), Error::DepthExceeded(|n| n == 128));
+}
+
+#[test]
+fn option() {
+    let mut reader = Options::new()
+        .with_max_depth(2)
+        .build_reader([1, 1, 0].as_slice());
+    assert_eq!(
+        reader.deserialize::<Option<Option<Option<u8>>>>().unwrap(),
+        Some(Some(None))
+    );
+
+    let mut reader = Options::new()
+        .with_max_depth(2)
+        .build_reader([1, 1, 1, 7].as_slice());
+    let err = reader
+        .deserialize::<Option<Option<Option<u8>>>>()
+        .unwrap_err();
+    assert_error!(err.into_inner(), Error::DepthExceeded(|n| n == 2));
+}
+
+#[test]
+fn seq() {
+    let buf = [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+
+    let mut reader = Options::new()
+        .with_max_depth(2)
+        .build_reader(buf.as_slice());
+    assert_eq!(
+        reader.deserialize::<Vec<Vec<u8>>>().unwrap(),
+        [Vec::<u8>::new()]
+    );
+
+    let mut reader = Options::new()
+        .with_max_depth(1)
+        .build_reader(buf.as_slice());
+    let err = reader.deserialize::<Vec<Vec<u8>>>().unwrap_err();
+    assert_error!(err.into_inner(), Error::DepthExceeded(|n| n == 1));
+}
+
+#[test]
+fn siblings() {
+    let mut reader = Options::new()
+        .with_max_depth(2)
+        .build_reader([1, 1, 1, 2, 1, 3].as_slice());
+    assert_eq!(
+        reader.deserialize::<(Option<u8>, Option<u8>)>().unwrap(),
+        (Some(1), Some(2))
+    );
+    assert_error!(
+        reader
+            .deserialize::<((Option<u8>,),)>()
+            .unwrap_err()
+            .into_inner(),
+        Error::DepthExceeded(|n| n == 2)
+    );
+}
@@ -0,0 +1,90 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Deserialize;
+
+use crate::options::Options;
+
+#[test]
+fn primitives() {
+    let mut reader = Options::new().with_little_endian().build_reader(
+        [
+            0x67, 0x12, 0x9A, 0x02, 0x00, 0x00, 0x00, 0x00, 0x80, 0x3F, 0xAF, 0xF4, 0x01, 0x00,
+        ]
+        .as_slice(),
+    );
+
+    assert_eq!(reader.read_u16().unwrap(), 4711);
+    assert_eq!(reader.read_i32().unwrap(), 666);
+    assert_eq!(reader.read_f32().unwrap(), 1.0);
+    assert_eq!(reader.deserialize::<char>().unwrap(), '💯');
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn big_endian() {
+    let mut reader = Options::new()
+        .with_little_endian()
+        .with_big_endian()
+        .build_reader([0x12, 0x67].as_slice());
+
+    assert_eq!(reader.read_u16().unwrap(), 4711);
+}
+
+#[test]
+fn str() {
+    let mut reader = Options::new().with_little_endian().build_reader(
+        [
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'a', b'b', b'c',
+        ]
+        .as_slice(),
+    );
+
+    assert_eq!(reader.deserialize::<&str>().unwrap(), "abc");
+}
+
+#[test]
+fn vec() {
+    let mut reader = Options::new().with_little_endian().build_reader(
+        [
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00,
+        ]
+        .as_slice(),
+    );
+
+    assert_eq!(reader.deserialize::<Vec<u16>>().unwrap(), [1, 2]);
+}
+
+#[test]
+fn r#enum() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Enum {
+        V1,
+        V2(u16),
+    }
+
+    let mut reader = Options::new()
+        .with_little_endian()
+        .build_reader([0x01, 0x00, 0x00, 0x00, 0x67, 0x12].as_slice());
+
+    assert_eq!(reader.deserialize::<Enum>().unwrap(), Enum::V2(4711));
+}
@@ -0,0 +1,211 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+
+use super::{roundtrip, serialize, v1, v2, V1, V2};
+use crate::assert_error;
+use crate::error::{Error, PathSegment};
+use crate::options::{Envelope, Options};
+use crate::source::ReadSource;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Outer<T> {
+    inner: T,
+    x: u8,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum E1 {
+    A,
+    B(u8),
+    C(u8),
+    D { x: u8 },
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum E2 {
+    A(u8),
+    B(u8, u8),
+    C { x: u8, y: u8 },
+    D { x: u8, y: u8 },
+}
+
+#[test]
+fn all_envelopes() {
+    for envelope in [Envelope::None, Envelope::Structs, Envelope::All] {
+        roundtrip(Options::new().with_envelope(envelope), v2());
+    }
+}
+
+#[test]
+fn appended_fields() {
+    let options = Options::new().with_envelope(Envelope::Structs);
+
+    let buf = serialize(options, &Outer { inner: v2(), x: 7 });
+    let mut reader = options.build_reader(buf.as_slice());
+
+    assert_eq!(
+        reader.deserialize::<Outer<V1>>().unwrap(),
+        Outer { inner: v1(), x: 7 }
+    );
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn appended_payload() {
+    let options = Options::new().with_envelope(Envelope::All);
+
+    let buf = serialize(
+        options,
+        &[
+            E2::A(1),
+            E2::B(2, 3),
+            E2::C { x: 4, y: 5 },
+            E2::D { x: 6, y: 7 },
+        ],
+    );
+    let mut reader = options.build_reader(buf.as_slice());
+
+    assert_eq!(
+        reader.deserialize::<[E1; 4]>().unwrap(),
+        [E1::A, E1::B(2), E1::C(4), E1::D { x: 6 }]
+    );
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn tagged_fields() {
+    let options = Options::new()
+        .with_field_indices()
+        .with_envelope(Envelope::All);
+
+    let buf = serialize(options, &Outer { inner: v2(), x: 7 });
+    let mut reader = options.build_reader(buf.as_slice());
+
+    assert_eq!(
+        reader.deserialize::<Outer<V1>>().unwrap(),
+        Outer { inner: v1(), x: 7 }
+    );
+}
+
+#[test]
+fn self_describing() {
+    let options = Options::new()
+        .with_self_describing()
+        .with_envelope(Envelope::All);
+
+    let buf = serialize(options, &v2());
+    assert_eq!(buf[0], 20);
+
+    let mut reader = options.build_reader(buf.as_slice());
+    assert_eq!(reader.deserialize::<V2>().unwrap(), v2());
+}
+
+#[test]
+fn overrun() {
+    let options = Options::new().with_envelope(Envelope::Structs);
+
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 5,             // envelope of 5 bytes
+        1,                                  // a
+        0, 0, 0, 0, 0, 0, 0, 1, b'x',       // b
+    ];
+    let mut reader = options.build_reader(buf.as_slice());
+
+    let err = reader.deserialize::<V1>().unwrap_err();
+    assert_eq!(err.position(), Some(18));
+    assert_error!(err.into_inner(), Error::Overrun(|n| n == 5));
+}
+
+#[test]
+fn truncated() {
+    let options = Options::new().with_envelope(Envelope::Structs);
+
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 20,            // envelope of 20 bytes
+        1,                                  // a
+        0, 0, 0, 0, 0, 0, 0, 1, b'x',       // b
+    ];
+    let mut reader = options.build_reader(buf.as_slice());
+
+    let err = reader.deserialize::<V1>().unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn path() {
+    let options = Options::new().with_envelope(Envelope::All);
+
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 3,                         // D
+        0, 0, 0, 0, 0, 0, 0, 0,             // empty payload
+        9,                                  // x outside of the payload
+    ];
+    let mut reader = options.build_reader(buf.as_slice());
+
+    let err = reader.deserialize::<E1>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Variant(3)]);
+    assert_error!(err.into_inner(), Error::Overrun(|n| n == 0));
+}
+
+#[test]
+fn hostile_length() {
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 1, 0, 0, 0, 0, 0,             // envelope of 2^40 bytes
+        1,                                  // inner
+        2,                                  // x
+    ];
+
+    let mut reader = Options::new()
+        .with_envelope(Envelope::Structs)
+        .with_item_limit(1024)
+        .build_reader(ReadSource::new(Cursor::new(buf)));
+    let err = reader.deserialize::<Outer<u8>>().unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 1 << 40));
+
+    // without a limit the rest is skipped without allocating it
+    let mut reader = Options::new()
+        .with_envelope(Envelope::Structs)
+        .build_reader(ReadSource::new(Cursor::new(buf)));
+    let err = reader.deserialize::<Outer<u8>>().unwrap_err();
+    assert_eq!(err.position(), Some(10));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_some()));
+}
+
+#[test]
+fn hostile_payload_length() {
+    let buf = [0, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 1]; // B with 2^40 bytes
+
+    let mut reader = Options::new()
+        .with_envelope(Envelope::All)
+        .with_item_limit(1024)
+        .build_reader(ReadSource::new(Cursor::new(buf)));
+    let err = reader.deserialize::<E1>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Variant(1)]);
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 1 << 40));
+}
@@ -0,0 +1,241 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+
+use super::{roundtrip, serialize, v1, v2, V1, V2};
+use crate::assert_error;
+use crate::error::{Error, PathSegment};
+use crate::options::Options;
+use crate::source::ReadSource;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Renamed {
+    b: String,
+    #[serde(default)]
+    x: u8,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Enum {
+    V1 { a: u8, b: String },
+    V2 { a: u8, b: String, c: Vec<u16> },
+}
+
+fn options() -> [Options; 6] {
+    [
+        Options::new().with_field_names(),
+        Options::new().with_field_indices(),
+        Options::new().with_varint().with_field_indices(),
+        Options::new().with_self_describing().with_field_names(),
+        Options::new().with_self_describing().with_field_indices(),
+        Options::new().with_varint().with_field_names(),
+    ]
+}
+
+#[test]
+fn all_options() {
+    for options in options() {
+        roundtrip(options, v2());
+    }
+}
+
+#[test]
+fn missing_field() {
+    for options in options() {
+        let buf = serialize(options, &v1());
+        let mut reader = options.build_reader(buf.as_slice());
+
+        assert_eq!(
+            reader.deserialize::<V2>().unwrap(),
+            V2 {
+                a: 1,
+                b: String::from("abc"),
+                c: vec![],
+            }
+        );
+        assert_eq!(reader.as_ref(), &[]);
+    }
+}
+
+#[test]
+fn unknown_field() {
+    for options in options() {
+        let mut buf = serialize(options, &v2());
+        buf.push(7);
+
+        let mut reader = options.build_reader(buf.as_slice());
+        assert_eq!(reader.deserialize::<V1>().unwrap(), v1());
+        assert_eq!(reader.as_ref(), &[7]);
+    }
+}
+
+#[test]
+fn internally_tagged() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(tag = "t")]
+    enum Internal {
+        A { x: u8 },
+        B { y: String },
+    }
+
+    for options in options() {
+        roundtrip(options.with_self_describing(), Internal::A { x: 1 });
+        roundtrip(
+            options.with_self_describing(),
+            Internal::B {
+                y: String::from("abc"),
+            },
+        );
+    }
+}
+
+#[test]
+fn renamed_field() {
+    let options = Options::new().with_field_names();
+
+    let buf = serialize(options, &v1());
+    let mut reader = options.build_reader(buf.as_slice());
+
+    assert_eq!(
+        reader.deserialize::<Renamed>().unwrap(),
+        Renamed {
+            b: String::from("abc"),
+            x: 0
+        }
+    );
+}
+
+#[test]
+fn struct_variant() {
+    let options = Options::new().with_field_names();
+
+    let buf = serialize(
+        options,
+        &Enum::V2 {
+            a: 1,
+            b: String::from("abc"),
+            c: vec![1, 2],
+        },
+    );
+    let mut reader = options.build_reader(buf.as_slice());
+
+    assert_eq!(
+        reader.deserialize::<Enum>().unwrap(),
+        Enum::V2 {
+            a: 1,
+            b: String::from("abc"),
+            c: vec![1, 2],
+        }
+    );
+}
+
+#[test]
+fn missing_required_field() {
+    let options = Options::new().with_field_names();
+
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 1,             // 1 field
+        0, 0, 0, 0, 0, 0, 0, 1, b'a',       // a
+        0, 0, 0, 0, 0, 0, 0, 1, 7,          // value
+    ];
+    let mut reader = options.build_reader(buf.as_slice());
+
+    let err = reader.deserialize::<V1>().unwrap_err();
+    assert_error!(
+        err.into_inner(),
+        Error::Serde(|msg| msg == "missing field `b`")
+    );
+}
+
+#[test]
+fn overrun() {
+    let options = Options::new().with_field_names();
+
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 1,             // 1 field
+        0, 0, 0, 0, 0, 0, 0, 1, b'b',       // b
+        0, 0, 0, 0, 0, 0, 0, 1,             // value with 1 byte
+        0, 0, 0, 0, 0, 0, 0, 1, b'x',       // but a string is encoded
+    ];
+    let mut reader = options.build_reader(buf.as_slice());
+
+    let err = reader.deserialize::<V1>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Field("b")]);
+    assert_error!(err.into_inner(), Error::Overrun(|n| n == 1));
+}
+
+#[test]
+fn path() {
+    let options = Options::new().with_field_indices();
+
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 1,             // 1 field
+        0, 0, 0, 1,                         // b
+        0, 0, 0, 0, 0, 0, 0, 9,             // value with 9 bytes
+        0, 0, 0, 0, 0, 0, 0, 1, 0xFF,       // invalid string
+    ];
+    let mut reader = options.build_reader(buf.as_slice());
+
+    let err = reader.deserialize::<V1>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Field("b")]);
+    assert_error!(err.into_inner(), Error::InvalidString(|_cause| true));
+}
+
+#[test]
+fn positional() {
+    let buf = serialize(Options::new(), &v1());
+    assert_eq!(buf, [1, 0, 0, 0, 0, 0, 0, 0, 3, b'a', b'b', b'c']);
+
+    let mut reader = Options::new().build_reader(buf.as_slice());
+    assert_eq!(reader.deserialize::<V1>().unwrap(), v1());
+}
+
+#[test]
+fn hostile_length() {
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 1,             // 1 field
+        0, 0, 0, 0, 0, 0, 0, 1, b'x',       // unknown field
+        0, 0, 1, 0, 0, 0, 0, 0,             // value with 2^40 bytes
+        1,
+    ];
+
+    let mut reader = Options::new()
+        .with_field_names()
+        .with_item_limit(1024)
+        .build_reader(ReadSource::new(Cursor::new(buf)));
+    let err = reader.deserialize::<V1>().unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 1 << 40));
+
+    // without a limit the value is skipped without allocating it
+    let mut reader = Options::new()
+        .with_field_names()
+        .build_reader(ReadSource::new(Cursor::new(buf)));
+    let err = reader.deserialize::<V1>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Index(0)]);
+    assert_error!(err.into_inner(), Error::Eof(|_cause| true));
+}
@@ -0,0 +1,112 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use std::io::Cursor;
+
+use crate::error::Error;
+use crate::options::Options;
+use crate::reader::{from_reader, from_slice, take_from_slice};
+use crate::{assert_error, assert_error_eq};
+
+#[test]
+fn from_slice_exact() {
+    assert_eq!(from_slice::<u16>(&[0x12, 0x67]).unwrap(), 4711);
+}
+
+#[test]
+fn from_slice_trailing() {
+    let err = from_slice::<u16>(&[0x12, 0x67, 0x00]).unwrap_err();
+    assert_error_eq!(err.into_inner(), Error::TrailingBytes(|n| 1));
+}
+
+#[test]
+fn from_slice_eof() {
+    let err = from_slice::<u16>(&[0x12]).unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn take_from_slice_exact() {
+    let (n, rest) = take_from_slice::<u16>(&[0x12, 0x67]).unwrap();
+    assert_eq!(n, 4711);
+    assert_eq!(rest, []);
+}
+
+#[test]
+fn take_from_slice_trailing() {
+    let (n, rest) = take_from_slice::<u16>(&[0x12, 0x67, 0x00]).unwrap();
+    assert_eq!(n, 4711);
+    assert_eq!(rest, [0x00]);
+}
+
+#[test]
+fn from_reader_exact() {
+    let mut cursor = Cursor::new([0x12, 0x67]);
+
+    assert_eq!(from_reader::<_, u16>(&mut cursor).unwrap(), 4711);
+    assert_eq!(cursor.position(), 2);
+}
+
+#[test]
+fn from_reader_trailing() {
+    let mut cursor = Cursor::new([0x12, 0x67, 0x00]);
+
+    assert_eq!(from_reader::<_, u16>(&mut cursor).unwrap(), 4711);
+    assert_eq!(cursor.position(), 2);
+}
+
+#[test]
+fn from_reader_eof() {
+    let err = from_reader::<_, u16>(Cursor::new([0x12])).unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_some()));
+}
+
+#[test]
+fn options() {
+    let options = Options::new().with_little_endian();
+
+    assert_eq!(options.from_slice::<u16>(&[0x67, 0x12]).unwrap(), 4711);
+    assert_eq!(
+        options.take_from_slice::<u16>(&[0x67, 0x12, 0x00]).unwrap(),
+        (4711, [0x00].as_slice())
+    );
+    assert_eq!(
+        options
+            .from_reader::<_, u16>(Cursor::new([0x67, 0x12]))
+            .unwrap(),
+        4711
+    );
+}
+
+#[test]
+fn from_reader_limit() {
+    let buf = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, b'x'];
+
+    let err = Options::new()
+        .with_item_limit(1024)
+        .from_reader::<_, String>(Cursor::new(buf))
+        .unwrap_err();
+    assert_error!(
+        err.into_inner(),
+        Error::LimitExceeded(|n| n == 0xFFFF_FFFF_FFFF_FF00)
+    );
+}
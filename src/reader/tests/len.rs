@@ -0,0 +1,53 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::reader::checked_len;
+
+#[test]
+fn usize() {
+    assert_eq!(checked_len::<usize>(0).unwrap(), 0);
+    assert_eq!(checked_len::<usize>(4711).unwrap(), 4711);
+}
+
+#[test]
+fn u32() {
+    // Simulates a 32-bit target.
+    assert_eq!(checked_len::<u32>(0).unwrap(), 0);
+    assert_eq!(checked_len::<u32>(u32::MAX as u64).unwrap(), u32::MAX);
+
+    let err = checked_len::<u32>(u32::MAX as u64 + 1).unwrap_err();
+    assert_error!(err, Error::LengthTooLarge(|n| n == u32::MAX as u64 + 1));
+
+    let err = checked_len::<u32>(u64::MAX).unwrap_err();
+    assert_error!(err, Error::LengthTooLarge(|n| n == u64::MAX));
+}
+
+#[test]
+fn u16() {
+    // Simulates a 16-bit target.
+    assert_eq!(checked_len::<u16>(u16::MAX as u64).unwrap(), u16::MAX);
+
+    let err = checked_len::<u16>(u16::MAX as u64 + 1).unwrap_err();
+    assert_error!(err, Error::LengthTooLarge(|n| n == u16::MAX as u64 + 1));
+}
@@ -0,0 +1,119 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::Options;
+use crate::source::ReadSource;
+
+#[test]
+fn item_str() {
+    let buf = [0, 0, 0, 0, 0, 0, 0, 3, b'a', b'b', b'c'];
+
+    let mut reader = Options::new()
+        .with_item_limit(3)
+        .build_reader(buf.as_slice());
+    assert_eq!(String::deserialize(&mut reader).unwrap(), "abc");
+
+    let mut reader = Options::new()
+        .with_item_limit(2)
+        .build_reader(buf.as_slice());
+    let err = String::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 3));
+}
+
+#[test]
+fn item_bytes_huge() {
+    let source = ReadSource::new(Cursor::new([0xFF; 8]));
+    let mut reader = Options::new().with_item_limit(1024).build_reader(source);
+    let err = reader.deserialize::<Vec<u8>>().unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == u64::MAX));
+}
+
+#[test]
+fn item_seq() {
+    let buf = [0, 0, 0, 0, 0, 0, 0, 3, 1, 2, 3];
+
+    let mut reader = Options::new()
+        .with_item_limit(3)
+        .build_reader(buf.as_slice());
+    assert_eq!(Vec::<u8>::deserialize(&mut reader).unwrap(), [1, 2, 3]);
+
+    let mut reader = Options::new()
+        .with_item_limit(2)
+        .build_reader(buf.as_slice());
+    let err = Vec::<u8>::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 3));
+}
+
+#[test]
+fn item_map() {
+    let buf = [0, 0, 0, 0, 0, 0, 0, 2, 1, 2, 3, 4];
+
+    let mut reader = Options::new()
+        .with_item_limit(1)
+        .build_reader(buf.as_slice());
+    let err = HashMap::<u8, u8>::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 2));
+}
+
+#[test]
+fn total() {
+    let buf = [0, 0, 0, 0, 0, 0, 0, 3, b'a', b'b', b'c'];
+
+    let mut reader = Options::new()
+        .with_total_limit(11)
+        .build_reader(buf.as_slice());
+    assert_eq!(String::deserialize(&mut reader).unwrap(), "abc");
+
+    let mut reader = Options::new()
+        .with_total_limit(10)
+        .build_reader(buf.as_slice());
+    let err = String::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 3));
+    assert_eq!(reader.as_ref(), b"abc");
+}
+
+#[test]
+fn total_across_values() {
+    let mut reader = Options::new()
+        .with_total_limit(5)
+        .build_reader([0, 0, 0, 1, 2, 3].as_slice());
+    assert_eq!(reader.read_u32().unwrap(), 1);
+    assert_eq!(reader.read_u8().unwrap(), 2);
+
+    let err = reader.read_u8().unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == 1));
+    assert_eq!(reader.as_ref(), &[3]);
+}
+
+#[test]
+fn total_huge() {
+    let source = ReadSource::new(Cursor::new([0xFF; 8]));
+    let mut reader = Options::new().with_total_limit(1024).build_reader(source);
+    let err = reader.deserialize::<String>().unwrap_err();
+    assert_error!(err.into_inner(), Error::LimitExceeded(|n| n == u64::MAX));
+}
@@ -0,0 +1,198 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::assert_error;
+use crate::error::{Error, PathSegment};
+use crate::reader::Reader;
+
+#[derive(Debug, Deserialize)]
+struct Key {
+    _id: u8,
+    _iv: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    _version: u8,
+    _keys: Vec<Key>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+enum Kind {
+    A,
+    B(u16),
+    C { n: u8, s: String },
+}
+
+#[test]
+fn struct_seq_struct() {
+    #[rustfmt::skip]
+    let buf = [
+        1,                               // version
+        0, 0, 0, 0, 0, 0, 0, 2,          // keys.len()
+        1, 0, 0, 0, 0, 0, 0, 0, 1, b'a', // keys[0]
+        2, 0, 0, 0, 0, 0, 0, 0, 1, 0xFF, // keys[1], invalid iv
+    ];
+    let mut reader = Reader::new(buf.as_slice());
+
+    let err = reader.deserialize::<Header>().unwrap_err();
+    assert_eq!(
+        err.path(),
+        [
+            PathSegment::Field("_keys"),
+            PathSegment::Index(1),
+            PathSegment::Field("_iv")
+        ]
+    );
+    assert_eq!(err.position(), Some(29));
+    assert_eq!(
+        err.to_string(),
+        "at byte 29 (_keys[1]._iv): not a string: invalid utf-8 sequence of 1 bytes from index 0"
+    );
+    assert_error!(err.into_inner(), Error::InvalidString(|_cause| true));
+}
+
+#[test]
+fn eof() {
+    let mut reader = Reader::new([1, 0, 0, 0, 0, 0, 0, 0, 1, 1].as_slice());
+
+    let err = reader.deserialize::<Header>().unwrap_err();
+    assert_eq!(
+        err.path(),
+        [
+            PathSegment::Field("_keys"),
+            PathSegment::Index(0),
+            PathSegment::Field("_iv")
+        ]
+    );
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn tuple() {
+    let mut reader = Reader::new([1, 2].as_slice());
+
+    let err = reader.deserialize::<(u8, u8, u8)>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Index(2)]);
+    assert_eq!(
+        err.to_string(),
+        "at byte 2 ([2]): No more bytes are available for reading."
+    );
+}
+
+#[test]
+fn map() {
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 2, // len
+        1, 0, 0, 0, 0, 0, 0, 0, 0, // entry 0
+        2, 0, 0, 0, 0, 0, 0, 0, 1, // entry 1, value is truncated
+    ];
+    let mut reader = Reader::new(buf.as_slice());
+
+    let err = reader.deserialize::<BTreeMap<u8, String>>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Key(String::from("2"))]);
+    assert_eq!(
+        err.to_string(),
+        "at byte 26 ([\"2\"]): No more bytes are available for reading."
+    );
+}
+
+#[test]
+fn map_str_key() {
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 1,          // len
+        0, 0, 0, 0, 0, 0, 0, 1, b'a', 7, // entry "a", value is truncated
+    ];
+    let mut reader = Reader::new(buf.as_slice());
+
+    let err = reader.deserialize::<BTreeMap<String, u16>>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Key(String::from("a"))]);
+}
+
+#[test]
+fn map_key() {
+    let mut reader = Reader::new([0, 0, 0, 0, 0, 0, 0, 1, 0].as_slice());
+
+    let err = reader.deserialize::<BTreeMap<u16, u8>>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Entry(0)]);
+}
+
+#[test]
+fn map_compound_key() {
+    #[rustfmt::skip]
+    let buf = [
+        0, 0, 0, 0, 0, 0, 0, 1, // len
+        1, 2,                   // entry (1, 2), value is missing
+    ];
+    let mut reader = Reader::new(buf.as_slice());
+
+    let err = reader.deserialize::<BTreeMap<(u8, u8), u8>>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Entry(0)]);
+}
+
+#[test]
+fn newtype_variant() {
+    let mut reader = Reader::new([0, 0, 0, 1, 0].as_slice());
+
+    let err = reader.deserialize::<Kind>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Variant(1)]);
+    assert_eq!(
+        err.to_string(),
+        "at byte 4 (::1): No more bytes are available for reading."
+    );
+}
+
+#[test]
+fn struct_variant() {
+    let mut reader = Reader::new([0, 0, 0, 2, 7, 0, 0].as_slice());
+
+    let err = reader.deserialize::<Kind>().unwrap_err();
+    assert_eq!(
+        err.path(),
+        [PathSegment::Variant(2), PathSegment::Field("s")]
+    );
+    assert_eq!(
+        err.to_string(),
+        "at byte 5 (::2.s): No more bytes are available for reading."
+    );
+}
+
+#[test]
+fn invalid_variant() {
+    let mut reader = Reader::new([0, 0, 0, 3].as_slice());
+
+    let err = reader.deserialize::<Kind>().unwrap_err();
+    assert!(err.path().is_empty());
+    assert_eq!(err.position(), Some(4));
+    assert_error!(err.into_inner(), Error::Serde(|_msg| true));
+}
+
+#[test]
+fn no_context() {
+    assert!(Error::MissingKey.path().is_empty());
+}
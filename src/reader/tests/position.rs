@@ -0,0 +1,134 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Deserialize;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::Options;
+use crate::reader::Reader;
+
+#[derive(Debug, Deserialize)]
+struct Sample {
+    _f1: u8,
+    _f2: String,
+}
+
+#[test]
+fn position() {
+    let mut reader = Reader::new([1, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, b'a'].as_slice());
+    assert_eq!(reader.position(), 0);
+
+    reader.read_u8().unwrap();
+    assert_eq!(reader.position(), 1);
+
+    reader.read_u16().unwrap();
+    assert_eq!(reader.position(), 3);
+
+    reader.deserialize::<String>().unwrap();
+    assert_eq!(reader.position(), 12);
+}
+
+#[test]
+fn varint() {
+    let mut reader = Options::new()
+        .with_varint()
+        .build_reader([0x80, 0x01, 0x01].as_slice());
+
+    reader.read_u32().unwrap();
+    assert_eq!(reader.position(), 2);
+    reader.read_u32().unwrap();
+    assert_eq!(reader.position(), 3);
+}
+
+#[test]
+fn read_eof() {
+    let mut reader = Reader::new([1, 2, 3].as_slice());
+
+    reader.read_u16().unwrap();
+
+    let err = reader.read_u16().unwrap_err();
+    assert_eq!(err.position(), Some(2));
+    assert_error!(err, Error::Context { |position| position == 2, |path| path.is_empty(), |cause| matches!(*cause, Error::Eof(None)) });
+}
+
+#[test]
+fn read_bytes_eof() {
+    let mut reader = Reader::new([1, 2, 3].as_slice());
+
+    reader.read_bytes(1).unwrap();
+
+    let err = reader.read_bytes(3).unwrap_err();
+    assert_eq!(err.position(), Some(1));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn deserialize_eof() {
+    let mut reader = Reader::new([7, 0, 0, 0, 0, 0, 0, 0, 2, b'a'].as_slice());
+
+    let err = reader.deserialize::<Sample>().unwrap_err();
+    assert_eq!(err.position(), Some(9));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn deserialize_invalid_char() {
+    let mut reader = Reader::new([0x00, 0x11, 0x00, 0x00].as_slice());
+
+    let err = reader.deserialize::<char>().unwrap_err();
+    assert_eq!(err.position(), Some(4));
+    assert_error!(err.into_inner(), Error::InvalidChar(|n| n == 0x110000));
+}
+
+#[test]
+fn deserialize_invalid_string() {
+    let mut reader = Reader::new([7, 0, 0, 0, 0, 0, 0, 0, 1, 0xFF].as_slice());
+
+    let err = reader.deserialize::<Sample>().unwrap_err();
+    assert_eq!(err.position(), Some(10));
+    assert_error!(err.into_inner(), Error::InvalidString(|_cause| true));
+}
+
+#[test]
+fn finish() {
+    let mut reader = Reader::new([1, 2, 3].as_slice());
+
+    reader.read_u8().unwrap();
+
+    let err = reader.finish().unwrap_err();
+    assert_eq!(err.position(), Some(1));
+    assert_error!(err.into_inner(), Error::TrailingBytes(|n| n == 2));
+}
+
+#[test]
+fn display() {
+    let mut reader = Reader::new([1, 2, 3].as_slice());
+
+    reader.read_u16().unwrap();
+
+    let err = reader.read_u16().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "at byte 2: No more bytes are available for reading."
+    );
+}
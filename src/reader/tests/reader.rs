@@ -21,10 +21,12 @@
 // IN THE SOFTWARE.
 
 use std::borrow::Cow;
+use std::io::{self, Cursor};
 
-use crate::assert_error;
 use crate::error::Error;
 use crate::reader::Reader;
+use crate::source::{ReadSource, TakeBytes};
+use crate::{assert_error, assert_error_eq};
 
 #[test]
 fn i8() {
@@ -40,7 +42,7 @@ fn i8() {
     assert_eq!(reader.as_ref(), &[]);
 
     let err = reader.read_i8().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[]);
 }
 
@@ -58,7 +60,7 @@ fn u8() {
     assert_eq!(reader.as_ref(), &[]);
 
     let err = reader.read_u8().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[]);
 }
 
@@ -76,7 +78,7 @@ fn i16() {
     assert_eq!(reader.as_ref(), &[0x02]);
 
     let err = reader.read_i16().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[0x02]);
 }
 
@@ -91,7 +93,7 @@ fn u16() {
     assert_eq!(reader.as_ref(), &[5]);
 
     let err = reader.read_u16().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[5]);
 }
 
@@ -117,7 +119,7 @@ fn i32() {
     assert_eq!(reader.as_ref(), &[0x02]);
 
     let err = reader.read_u32().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[0x02]);
 }
 
@@ -132,7 +134,7 @@ fn u32() {
     assert_eq!(reader.as_ref(), &[9, 10, 11]);
 
     let err = reader.read_u32().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[9, 10, 11]);
 }
 
@@ -165,7 +167,7 @@ fn i64() {
     assert_eq!(reader.as_ref(), &[0x02]);
 
     let err = reader.read_i64().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[0x02]);
 }
 
@@ -188,7 +190,7 @@ fn u64() {
     assert_eq!(reader.as_ref(), &[17, 18, 19, 20, 21, 22, 23]);
 
     let err = reader.read_u64().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[17, 18, 19, 20, 21, 22, 23]);
 }
 
@@ -227,7 +229,7 @@ fn i128() {
     assert_eq!(reader.as_ref(), &[0x02]);
 
     let err = reader.read_i128().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[0x02]);
 }
 
@@ -264,13 +266,53 @@ fn u128() {
     );
 
     let err = reader.read_u128().unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(
         reader.as_ref(),
         &[33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,]
     );
 }
 
+#[test]
+fn f32() {
+    let mut reader =
+        Reader::new([0x3F, 0x80, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x01, 0x02].as_slice());
+
+    assert_eq!(reader.read_f32().unwrap(), 1.0);
+    assert_eq!(reader.as_ref(), &[0xC0, 0x00, 0x00, 0x00, 0x01, 0x02]);
+
+    assert_eq!(reader.read_f32().unwrap(), -2.0);
+    assert_eq!(reader.as_ref(), &[0x01, 0x02]);
+
+    let err = reader.read_f32().unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+    assert_eq!(reader.as_ref(), &[0x01, 0x02]);
+}
+
+#[test]
+fn f64() {
+    let mut reader = Reader::new(
+        [
+            0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x01, 0x02,
+        ]
+        .as_slice(),
+    );
+
+    assert_eq!(reader.read_f64().unwrap(), 1.0);
+    assert_eq!(
+        reader.as_ref(),
+        &[0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02]
+    );
+
+    assert_eq!(reader.read_f64().unwrap(), -2.0);
+    assert_eq!(reader.as_ref(), &[0x01, 0x02]);
+
+    let err = reader.read_f64().unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+    assert_eq!(reader.as_ref(), &[0x01, 0x02]);
+}
+
 #[test]
 fn read_bytes() {
     let mut reader = Reader::new([1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice());
@@ -288,7 +330,7 @@ fn read_bytes() {
     assert_eq!(reader.as_ref(), &[7, 8, 9]);
 
     let err = reader.read_bytes(4).unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(reader.as_ref(), &[7, 8, 9]);
 }
 
@@ -317,7 +359,64 @@ fn read_bytes_to() {
 
     let mut buf = [0; 4];
     let err = reader.read_bytes_to(&mut buf).unwrap_err();
-    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
     assert_eq!(buf, [0, 0, 0, 0]);
     assert_eq!(reader.as_ref(), &[7, 8, 9]);
 }
+
+#[test]
+fn hostile_length_read_source() {
+    let buf = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, b'x'];
+
+    let mut reader = Reader::new(ReadSource::new(Cursor::new(buf)));
+    let err = reader.deserialize::<String>().unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_some()));
+}
+
+#[test]
+fn finish() {
+    let mut reader = Reader::new([1, 2].as_slice());
+    assert_eq!(reader.read_u16().unwrap(), 0x0102);
+    reader.finish().unwrap();
+
+    let mut reader = Reader::new([1, 2, 3].as_slice());
+    assert_eq!(reader.read_u16().unwrap(), 0x0102);
+    let err = reader.finish().unwrap_err();
+    assert_error_eq!(err.into_inner(), Error::TrailingBytes(|n| 1));
+}
+
+#[test]
+fn finish_read_source() {
+    let mut reader = Reader::new(ReadSource::new(Cursor::new([1, 2])));
+    assert_eq!(reader.read_u16().unwrap(), 0x0102);
+    reader.finish().unwrap();
+
+    let mut reader = Reader::new(ReadSource::new(Cursor::new([1, 2, 3, 4])));
+    assert_eq!(reader.read_u16().unwrap(), 0x0102);
+    let err = reader.finish().unwrap_err();
+    assert_error_eq!(err.into_inner(), Error::TrailingBytes(|n| 1));
+
+    // the source is not drained
+    let mut reader = Reader::new(ReadSource::new(io::repeat(1)));
+    assert_eq!(reader.read_u16().unwrap(), 0x0101);
+    let err = reader.finish().unwrap_err();
+    assert_error_eq!(err.into_inner(), Error::TrailingBytes(|n| 1));
+}
+
+#[test]
+fn finish_unsupported() {
+    struct Source;
+
+    impl TakeBytes<'static> for Source {
+        fn take_bytes(&mut self, n: usize) -> crate::Result<Cow<'static, [u8]>> {
+            Ok(Cow::Owned(vec![0; n]))
+        }
+    }
+
+    let reader = Reader::new(Source);
+    let err = reader.finish().unwrap_err();
+    assert_error!(
+        err.into_inner(),
+        Error::Unsupported(|what| what == "remaining")
+    );
+}
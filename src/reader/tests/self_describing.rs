@@ -0,0 +1,306 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use crate::assert_error;
+use crate::error::{Error, PathSegment};
+use crate::options::Options;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Sample {
+    a: u8,
+    b: String,
+    c: Option<Vec<u16>>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Enum {
+    A,
+    B(u16),
+    C(u8, u8),
+    D { x: u8 },
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+enum Untagged {
+    Int(u32),
+    Str(String),
+    Seq(Vec<u8>),
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
+enum Internal {
+    A { x: u8 },
+    B { y: String },
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Inner {
+    a: u8,
+    b: u16,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Flatten {
+    x: u8,
+    #[serde(flatten)]
+    inner: Inner,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+enum UntaggedStruct {
+    C { q: u16 },
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Kind {
+    A,
+    B(u8),
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+enum UntaggedEnum {
+    K(Kind),
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Tagged {
+    kind: Kind,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct FlattenEnum {
+    x: u8,
+    #[serde(flatten)]
+    inner: Tagged,
+}
+
+fn roundtrip<T: Debug + PartialEq + Serialize + DeserializeOwned>(value: T) {
+    super::roundtrip(Options::new().with_self_describing(), value);
+}
+
+#[test]
+fn primitives() {
+    roundtrip(true);
+    roundtrip(-1i8);
+    roundtrip(i16::MIN);
+    roundtrip(i32::MIN);
+    roundtrip(i64::MIN);
+    roundtrip(i128::MIN);
+    roundtrip(u8::MAX);
+    roundtrip(u16::MAX);
+    roundtrip(u32::MAX);
+    roundtrip(u64::MAX);
+    roundtrip(u128::MAX);
+    roundtrip(1.5f32);
+    roundtrip(1.5f64);
+    roundtrip('x');
+    roundtrip(String::from("abc"));
+    roundtrip(());
+}
+
+#[test]
+fn compound() {
+    roundtrip(Some(1u8));
+    roundtrip(None::<u8>);
+    roundtrip(vec![1u16, 2, 3]);
+    roundtrip((1u8, String::from("a"), false));
+    roundtrip(BTreeMap::from([
+        (1u8, String::from("a")),
+        (2, String::from("b")),
+    ]));
+    roundtrip(Sample {
+        a: 1,
+        b: String::from("x"),
+        c: Some(vec![1, 2]),
+    });
+}
+
+#[test]
+fn variants() {
+    roundtrip(Enum::A);
+    roundtrip(Enum::B(4711));
+    roundtrip(Enum::C(1, 2));
+    roundtrip(Enum::D { x: 7 });
+}
+
+#[test]
+fn untagged() {
+    roundtrip(Untagged::Int(1));
+    roundtrip(Untagged::Str(String::from("abc")));
+    roundtrip(Untagged::Seq(vec![1, 2]));
+}
+
+#[test]
+fn untagged_struct() {
+    roundtrip(UntaggedStruct::C { q: 4711 });
+}
+
+#[test]
+fn untagged_enum() {
+    roundtrip(UntaggedEnum::K(Kind::A));
+    roundtrip(UntaggedEnum::K(Kind::B(7)));
+}
+
+#[test]
+fn internally_tagged() {
+    roundtrip(Internal::A { x: 1 });
+    roundtrip(Internal::B {
+        y: String::from("abc"),
+    });
+}
+
+#[test]
+fn flatten() {
+    roundtrip(Flatten {
+        x: 1,
+        inner: Inner { a: 2, b: 3 },
+    });
+}
+
+#[test]
+fn flatten_enum() {
+    roundtrip(FlattenEnum {
+        x: 1,
+        inner: Tagged { kind: Kind::A },
+    });
+    roundtrip(FlattenEnum {
+        x: 1,
+        inner: Tagged { kind: Kind::B(2) },
+    });
+}
+
+#[test]
+fn unknown_field() {
+    #[rustfmt::skip]
+    let buf = [
+        20, 0, 0, 0, 0, 0, 0, 0, 3,                // struct with 3 fields
+        15, 0, 0, 0, 0, 0, 0, 0, 1, b'a', 7, 1,    // a
+        15, 0, 0, 0, 0, 0, 0, 0, 1, b'y', 7, 9,    // y, unknown
+        15, 0, 0, 0, 0, 0, 0, 0, 1, b'b', 8, 0, 2, // b
+    ];
+    let mut reader = Options::new()
+        .with_self_describing()
+        .build_reader(buf.as_slice());
+
+    assert_eq!(reader.deserialize::<Inner>().unwrap(), Inner { a: 1, b: 2 });
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn single_entry() {
+    let mut reader = Options::new()
+        .with_self_describing()
+        .build_reader([20, 0, 0, 0, 0, 0, 0, 0, 2].as_slice());
+    let err = reader.deserialize::<Enum>().unwrap_err();
+    assert_error!(
+        err.into_inner(),
+        Error::Serde(|msg| msg.contains("single entry"))
+    );
+}
+
+#[test]
+fn ignored_any() {
+    let options = Options::new().with_self_describing();
+
+    let mut writer = options.build_writer(vec![]);
+    writer
+        .serialize(&Sample {
+            a: 1,
+            b: String::from("x"),
+            c: None,
+        })
+        .unwrap();
+    writer.serialize(&7u8).unwrap();
+    let buf = writer.into_target();
+
+    let mut reader = options.build_reader(buf.as_slice());
+    reader.deserialize::<IgnoredAny>().unwrap();
+    assert_eq!(reader.deserialize::<u8>().unwrap(), 7);
+}
+
+#[test]
+fn widening() {
+    let mut reader = Options::new()
+        .with_self_describing()
+        .build_reader([7, 1].as_slice());
+    assert_eq!(reader.deserialize::<u64>().unwrap(), 1);
+}
+
+#[test]
+fn invalid_type() {
+    let mut reader = Options::new()
+        .with_self_describing()
+        .build_reader([22].as_slice());
+    let err = reader.deserialize::<u8>().unwrap_err();
+    assert_error!(err.into_inner(), Error::InvalidType(|n| n == 22));
+}
+
+#[test]
+fn type_mismatch() {
+    let mut reader = Options::new()
+        .with_self_describing()
+        .build_reader([15, 0, 0, 0, 0, 0, 0, 0, 0].as_slice());
+    let err = reader.deserialize::<u8>().unwrap_err();
+    assert_error!(err.into_inner(), Error::Serde(|_msg| true));
+}
+
+#[test]
+fn path() {
+    #[rustfmt::skip]
+    let buf = [
+        20, 0, 0, 0, 0, 0, 0, 0, 3,             // struct with 3 fields
+        15, 0, 0, 0, 0, 0, 0, 0, 1, b'a', 7, 1, // a
+        15, 0, 0, 0, 0, 0, 0, 0, 1, b'b',       // b
+        15, 0, 0, 0, 0, 0, 0, 0, 0,
+        15, 0, 0, 0, 0, 0, 0, 0, 1, b'c', 18, 7, // c, u8 instead of seq
+    ];
+    let mut reader = Options::new()
+        .with_self_describing()
+        .build_reader(buf.as_slice());
+
+    let err = reader.deserialize::<Sample>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Field("c")]);
+}
+
+#[test]
+fn compact_any() {
+    let mut reader = Options::new().build_reader([7, 1].as_slice());
+    let err = reader.deserialize::<IgnoredAny>();
+    assert!(err.is_ok());
+    assert_eq!(reader.as_ref(), &[7, 1]);
+
+    let mut reader = Options::new().build_reader([7, 1].as_slice());
+    let err = reader.deserialize::<Untagged>().unwrap_err();
+    assert_error!(
+        err.into_inner(),
+        Error::Unsupported(|what| what == "deserialize_any")
+    );
+}
@@ -0,0 +1,82 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::Options;
+
+#[test]
+fn bool() {
+    for (buf, b) in [([0], false), ([1], true)] {
+        let mut reader = Options::new().with_strict().build_reader(buf.as_slice());
+        assert_eq!(reader.deserialize::<bool>().unwrap(), b);
+    }
+}
+
+#[test]
+fn bool_invalid() {
+    for n in [2, 0xFF] {
+        let buf = [n];
+        let mut reader = Options::new().with_strict().build_reader(buf.as_slice());
+        let err = reader.deserialize::<bool>().unwrap_err();
+        assert_eq!(err.position(), Some(1));
+        assert_error!(err.into_inner(), Error::InvalidBool(|v| v == n));
+    }
+}
+
+#[test]
+fn bool_lenient() {
+    let mut reader = Options::new()
+        .with_strict()
+        .with_lenient()
+        .build_reader([2].as_slice());
+    assert!(reader.deserialize::<bool>().unwrap());
+}
+
+#[test]
+fn option() {
+    let mut reader = Options::new()
+        .with_strict()
+        .build_reader([0x00, 0x01, 0x00, 0x01].as_slice());
+    assert_eq!(reader.deserialize::<Option<u16>>().unwrap(), None);
+    assert_eq!(reader.deserialize::<Option<u16>>().unwrap(), Some(1));
+}
+
+#[test]
+fn option_invalid() {
+    for n in [2, 0xFF] {
+        let buf = [n, 0x00, 0x01];
+        let mut reader = Options::new().with_strict().build_reader(buf.as_slice());
+        let err = reader.deserialize::<Option<u16>>().unwrap_err();
+        assert_eq!(err.position(), Some(1));
+        assert_error!(err.into_inner(), Error::InvalidOptionTag(|v| v == n));
+    }
+}
+
+#[test]
+fn option_lenient() {
+    let mut reader = Options::new()
+        .with_strict()
+        .with_lenient()
+        .build_reader([0x02, 0x00, 0x01].as_slice());
+    assert_eq!(reader.deserialize::<Option<u16>>().unwrap(), Some(1));
+}
@@ -0,0 +1,149 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+use crate::assert_error;
+use crate::error::{Error, PathSegment};
+use crate::options::Options;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Enum {
+    A,
+    B(u16),
+    C(u8, u8),
+    D { x: u8 },
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Reordered {
+    X,
+    D { x: u8 },
+    C(u8, u8),
+    B(u16),
+    A,
+}
+
+fn roundtrip(options: Options, value: Enum) {
+    super::roundtrip(options.with_variant_names(), value);
+}
+
+#[test]
+fn compact() {
+    roundtrip(Options::new(), Enum::A);
+    roundtrip(Options::new(), Enum::B(4711));
+    roundtrip(Options::new(), Enum::C(1, 2));
+    roundtrip(Options::new(), Enum::D { x: 7 });
+}
+
+#[test]
+fn self_describing() {
+    let options = Options::new().with_self_describing();
+
+    roundtrip(options, Enum::A);
+    roundtrip(options, Enum::B(4711));
+    roundtrip(options, Enum::C(1, 2));
+    roundtrip(options, Enum::D { x: 7 });
+}
+
+#[test]
+fn reordered() {
+    let options = Options::new().with_variant_names();
+
+    let mut writer = options.build_writer(vec![]);
+    writer.serialize(&Enum::A).unwrap();
+    writer.serialize(&Enum::B(1)).unwrap();
+    writer.serialize(&Enum::C(2, 3)).unwrap();
+    writer.serialize(&Enum::D { x: 4 }).unwrap();
+    let buf = writer.into_target();
+
+    let mut reader = options.build_reader(buf.as_slice());
+    assert_eq!(reader.deserialize::<Reordered>().unwrap(), Reordered::A);
+    assert_eq!(reader.deserialize::<Reordered>().unwrap(), Reordered::B(1));
+    assert_eq!(
+        reader.deserialize::<Reordered>().unwrap(),
+        Reordered::C(2, 3)
+    );
+    assert_eq!(
+        reader.deserialize::<Reordered>().unwrap(),
+        Reordered::D { x: 4 }
+    );
+}
+
+#[test]
+fn unknown_variant() {
+    let mut reader = Options::new()
+        .with_variant_names()
+        .build_reader([0, 0, 0, 0, 0, 0, 0, 1, b'X'].as_slice());
+
+    let err = reader.deserialize::<Enum>().unwrap_err();
+    assert_error!(
+        err.into_inner(),
+        Error::Serde(|msg| msg.contains("unknown variant `X`"))
+    );
+}
+
+#[test]
+fn invalid_name() {
+    let mut reader = Options::new()
+        .with_variant_names()
+        .build_reader([0, 0, 0, 0, 0, 0, 0, 1, 0xFF].as_slice());
+
+    let err = reader.deserialize::<Enum>().unwrap_err();
+    assert_error!(err.into_inner(), Error::InvalidString(|_cause| true));
+}
+
+#[test]
+fn path() {
+    let mut reader = Options::new()
+        .with_variant_names()
+        .build_reader([0, 0, 0, 0, 0, 0, 0, 1, b'B', 1].as_slice());
+
+    let err = reader.deserialize::<Enum>().unwrap_err();
+    assert_eq!(err.path(), [PathSegment::Variant(1)]);
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn ignored_any() {
+    let options = Options::new().with_self_describing().with_variant_names();
+
+    let mut writer = options.build_writer(vec![]);
+    writer.serialize(&Enum::B(1)).unwrap();
+    writer.serialize(&7u8).unwrap();
+    let buf = writer.into_target();
+
+    let mut reader = options.build_reader(buf.as_slice());
+    reader.deserialize::<IgnoredAny>().unwrap();
+    assert_eq!(reader.deserialize::<u8>().unwrap(), 7);
+}
+
+#[test]
+fn indices() {
+    let mut reader = Options::new()
+        .with_variant_names()
+        .with_variant_indices()
+        .build_reader([0, 0, 0, 1, 0, 1].as_slice());
+
+    assert_eq!(reader.deserialize::<Enum>().unwrap(), Enum::B(1));
+}
@@ -0,0 +1,147 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Deserialize;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::Options;
+
+#[test]
+fn u16() {
+    for (buf, n) in [
+        (vec![0x00], 0),
+        (vec![0x01], 1),
+        (vec![0x7F], 127),
+        (vec![0x80, 0x01], 128),
+        (vec![0xE7, 0x24], 4711),
+        (vec![0xFF, 0xFF, 0x03], u16::MAX),
+    ] {
+        let mut reader = Options::new().with_varint().build_reader(buf.as_slice());
+        assert_eq!(reader.read_u16().unwrap(), n);
+        assert_eq!(reader.as_ref(), &[]);
+    }
+}
+
+#[test]
+fn u16_overflow() {
+    for buf in [vec![0xFF, 0xFF, 0x04], vec![0x80, 0x80, 0x80, 0x01]] {
+        let mut reader = Options::new().with_varint().build_reader(buf.as_slice());
+        let err = reader.read_u16().unwrap_err();
+        assert_error!(err.into_inner(), Error::InvalidVarint);
+    }
+}
+
+#[test]
+fn overlong() {
+    for buf in [vec![0x80, 0x00], vec![0x81, 0x80, 0x00]] {
+        let mut reader = Options::new().with_varint().build_reader(buf.as_slice());
+        let err = reader.read_u32().unwrap_err();
+        assert_error!(err.into_inner(), Error::InvalidVarint);
+    }
+}
+
+#[test]
+fn eof() {
+    let mut reader = Options::new()
+        .with_varint()
+        .build_reader([0x80, 0x80].as_slice());
+    let err = reader.read_u32().unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn u64() {
+    let mut reader = Options::new()
+        .with_varint()
+        .build_reader([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01].as_slice());
+    assert_eq!(reader.read_u64().unwrap(), u64::MAX);
+
+    let mut reader = Options::new()
+        .with_varint()
+        .build_reader([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02].as_slice());
+    let err = reader.read_u64().unwrap_err();
+    assert_error!(err.into_inner(), Error::InvalidVarint);
+}
+
+#[test]
+fn u128() {
+    let mut buf = vec![0xFF; 18];
+    buf.push(0x03);
+
+    let mut reader = Options::new().with_varint().build_reader(buf.as_slice());
+    assert_eq!(reader.read_u128().unwrap(), u128::MAX);
+}
+
+#[test]
+fn i32() {
+    for (buf, n) in [
+        (vec![0x00], 0),
+        (vec![0x01], -1),
+        (vec![0x02], 1),
+        (vec![0x03], -2),
+        (vec![0xFE, 0xFF, 0xFF, 0xFF, 0x0F], i32::MAX),
+        (vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F], i32::MIN),
+    ] {
+        let mut reader = Options::new().with_varint().build_reader(buf.as_slice());
+        assert_eq!(reader.read_i32().unwrap(), n);
+    }
+}
+
+#[test]
+fn u8() {
+    let mut reader = Options::new().with_varint().build_reader([0xFF].as_slice());
+    assert_eq!(reader.read_u8().unwrap(), 0xFF);
+}
+
+#[test]
+fn prefixes() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Enum {
+        V1,
+        V2(String, u16),
+    }
+
+    let mut reader = Options::new()
+        .with_varint_prefixes()
+        .build_reader([0x01, 0x03, b'a', b'b', b'c', 0x12, 0x67].as_slice());
+    assert_eq!(
+        reader.deserialize::<Enum>().unwrap(),
+        Enum::V2("abc".to_string(), 4711)
+    );
+
+    let mut reader = Options::new()
+        .with_varint()
+        .build_reader([0x01, 0x03, b'a', b'b', b'c', 0xE7, 0x24].as_slice());
+    assert_eq!(
+        reader.deserialize::<Enum>().unwrap(),
+        Enum::V2("abc".to_string(), 4711)
+    );
+}
+
+#[test]
+fn seq() {
+    let mut reader = Options::new()
+        .with_varint()
+        .build_reader([0x02, 0x01, 0x80, 0x01].as_slice());
+    assert_eq!(reader.deserialize::<Vec<u32>>().unwrap(), [1, 128]);
+}
@@ -0,0 +1,145 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Deserialize;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::{Options, Width};
+
+#[test]
+fn u8() {
+    let mut reader = Options::new()
+        .with_length_width(Width::U8)
+        .build_reader([3, b'a', b'b', b'c'].as_slice());
+    assert_eq!(String::deserialize(&mut reader).unwrap(), "abc");
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn u16() {
+    let mut reader = Options::new()
+        .with_length_width(Width::U16)
+        .build_reader([0, 2, 1, 2].as_slice());
+    assert_eq!(Vec::<u8>::deserialize(&mut reader).unwrap(), [1, 2]);
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn u16_little_endian() {
+    let mut reader = Options::new()
+        .with_little_endian()
+        .with_length_width(Width::U16)
+        .build_reader([2, 0, 1, 2].as_slice());
+    assert_eq!(Vec::<u8>::deserialize(&mut reader).unwrap(), [1, 2]);
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn u32() {
+    let mut reader = Options::new()
+        .with_length_width(Width::U32)
+        .build_reader([0, 0, 0, 1, b'a'].as_slice());
+    assert_eq!(String::deserialize(&mut reader).unwrap(), "a");
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn u8_eof() {
+    let mut reader = Options::new()
+        .with_length_width(Width::U8)
+        .build_reader([2, b'a'].as_slice());
+    let err = String::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::Eof(|cause| cause.is_none()));
+}
+
+#[test]
+fn varint_u8() {
+    let mut reader = Options::new()
+        .with_varint_prefixes()
+        .with_length_width(Width::U8)
+        .build_reader([0x01, b'a'].as_slice());
+    assert_eq!(String::deserialize(&mut reader).unwrap(), "a");
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn varint_u8_overflow() {
+    let mut reader = Options::new()
+        .with_varint_prefixes()
+        .with_length_width(Width::U8)
+        .build_reader([0x80, 0x02].as_slice());
+    let err = String::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::InvalidVarint);
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Enum {
+    A,
+    B(u8),
+}
+
+#[test]
+fn tag_u8() {
+    let mut reader = Options::new()
+        .with_tag_width(Width::U8)
+        .build_reader([1, 7].as_slice());
+    assert_eq!(Enum::deserialize(&mut reader).unwrap(), Enum::B(7));
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn tag_u16() {
+    let mut reader = Options::new()
+        .with_tag_width(Width::U16)
+        .build_reader([0, 0].as_slice());
+    assert_eq!(Enum::deserialize(&mut reader).unwrap(), Enum::A);
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn tag_u64() {
+    let mut reader = Options::new()
+        .with_tag_width(Width::U64)
+        .build_reader([0, 0, 0, 0, 0, 0, 0, 1, 7].as_slice());
+    assert_eq!(Enum::deserialize(&mut reader).unwrap(), Enum::B(7));
+    assert_eq!(reader.as_ref(), &[]);
+}
+
+#[test]
+fn tag_u64_invalid() {
+    let mut reader = Options::new()
+        .with_tag_width(Width::U64)
+        .build_reader([0, 0, 0, 1, 0, 0, 0, 0].as_slice());
+    let err = Enum::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::InvalidTag(|n| n == 1 << 32));
+}
+
+#[test]
+fn tag_varint_u8_overflow() {
+    let mut reader = Options::new()
+        .with_varint_prefixes()
+        .with_tag_width(Width::U8)
+        .build_reader([0x80, 0x02].as_slice());
+    let err = Enum::deserialize(&mut reader).unwrap_err();
+    assert_error!(err.into_inner(), Error::InvalidVarint);
+}
@@ -24,6 +24,7 @@
 mod tests;
 
 use std::borrow::Cow;
+use std::io::{self, Read};
 
 use crate::error::{Error, Result};
 #[cfg(doc)]
@@ -56,9 +57,41 @@ pub trait TakeBytes<'tb> {
     fn take_bytes_to(&mut self, buf: &mut [u8]) -> Result<()> {
         self.take_bytes(buf.len()).map(|bytes| {
             buf.copy_from_slice(bytes.as_ref());
-            ()
         })
     }
+
+    /// Skips `n` bytes of the source.
+    ///
+    /// The default implementation takes the bytes in small chunks, thus the
+    /// skipped data are never allocated at once.
+    ///
+    /// # Errors
+    ///
+    /// If not enough data are available an [`Error::Eof`] error is returned.
+    fn skip(&mut self, n: usize) -> Result<()> {
+        let mut buf = [0; 512];
+        let mut n = n;
+
+        while n > 0 {
+            let m = n.min(buf.len());
+
+            self.take_bytes_to(&mut buf[..m])?;
+            n -= m;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of bytes, which are still available in the source.
+    ///
+    /// A value of `0` means, that the source is exhausted. If the number of
+    /// bytes cannot be determined without consuming the data, a lower bound
+    /// greater than `0` may be returned.
+    ///
+    /// The default implementation returns an [`Error::Unsupported`] error.
+    fn remaining(&mut self) -> Result<usize> {
+        Err(Error::Unsupported("remaining"))
+    }
 }
 
 /// `TakeBytes` is implemented for `&[u8]` by taking the first part of the
@@ -78,4 +111,130 @@ impl<'tb> TakeBytes<'tb> for &'tb [u8] {
             Err(Error::Eof(None))
         }
     }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        self.take_bytes(n).map(|_| ())
+    }
+
+    fn remaining(&mut self) -> Result<usize> {
+        Ok(self.len())
+    }
+}
+
+/// A [`TakeBytes`] implementation that takes bytes from an [`io::Read`]
+/// instance.
+///
+/// Data cannot be borrowed from a reader, so [`TakeBytes::take_bytes`] always
+/// returns [`Cow::Owned`] data. [`TakeBytes::take_bytes_to`] reads directly
+/// into the given buffer.
+///
+/// If the reader reaches EOF before the requested number of bytes are read,
+/// an [`Error::Eof`] error is returned. The buffer of
+/// [`TakeBytes::take_bytes`] only grows as data arrive, thus a hostile length
+/// does not allocate memory in advance.
+///
+/// # Example
+///
+/// ```rust
+/// use nuts_bytes::{ReadSource, Reader};
+/// use std::io::Cursor;
+///
+/// let cursor = Cursor::new([0x00, 0x00, 0x02, 0x9A]);
+/// let mut reader = Reader::new(ReadSource::new(cursor));
+///
+/// let n: u32 = reader.deserialize().unwrap();
+/// assert_eq!(n, 666);
+/// ```
+///
+/// [`io::Read`]: std::io::Read
+#[derive(Debug)]
+pub struct ReadSource<R> {
+    inner: R,
+    peeked: Option<u8>,
+}
+
+impl<R: Read> ReadSource<R> {
+    /// Creates a new `ReadSource` instance, which takes bytes from the given
+    /// `inner` reader.
+    pub fn new(inner: R) -> ReadSource<R> {
+        ReadSource {
+            inner,
+            peeked: None,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `ReadSource`, returning the underlying reader.
+    ///
+    /// A byte probed by [`TakeBytes::remaining`], which was not taken yet, is
+    /// lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> TakeBytes<'static> for ReadSource<R> {
+    fn take_bytes(&mut self, n: usize) -> Result<Cow<'static, [u8]>> {
+        let mut buf = vec![];
+
+        if n > 0 {
+            buf.extend(self.peeked.take());
+        }
+
+        // the buffer only grows as data arrive
+        let m = (n - buf.len()) as u64;
+        (&mut self.inner).take(m).read_to_end(&mut buf)?;
+
+        if buf.len() == n {
+            Ok(Cow::Owned(buf))
+        } else {
+            Err(Error::eof(io::Error::from(io::ErrorKind::UnexpectedEof)))
+        }
+    }
+
+    fn take_bytes_to(&mut self, buf: &mut [u8]) -> Result<()> {
+        let buf = match (self.peeked, buf.split_first_mut()) {
+            (Some(byte), Some((first, rest))) => {
+                *first = byte;
+                self.peeked = None;
+                rest
+            }
+            _ => buf,
+        };
+
+        Ok(self.inner.read_exact(buf)?)
+    }
+
+    /// The underlying reader is probed by reading a single byte, which is
+    /// kept and returned by the next call to take bytes. Thus `1` is
+    /// returned, if data are left, even if there are more bytes available.
+    /// The reader is never drained.
+    fn remaining(&mut self) -> Result<usize> {
+        if self.peeked.is_some() {
+            return Ok(1);
+        }
+
+        let mut buf = [0; 1];
+
+        loop {
+            match self.inner.read(&mut buf) {
+                Ok(0) => return Ok(0),
+                Ok(_) => {
+                    self.peeked = Some(buf[0]);
+                    return Ok(1);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
 }
@@ -0,0 +1,163 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use std::borrow::Cow;
+use std::io::{self, Cursor, ErrorKind};
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::source::{ReadSource, TakeBytes};
+
+fn cause_is_eof(cause: &Option<Box<dyn std::error::Error + Send + Sync>>) -> bool {
+    cause
+        .as_ref()
+        .and_then(|err| err.downcast_ref::<std::io::Error>())
+        .is_some_and(|err| err.kind() == ErrorKind::UnexpectedEof)
+}
+
+#[test]
+fn take_bytes() {
+    let mut source = ReadSource::new(Cursor::new([1, 2, 3, 4, 5, 6, 7, 8, 9]));
+
+    assert_eq!(source.take_bytes(0).unwrap(), Cow::<[u8]>::Owned(vec![]));
+    assert_eq!(source.get_ref().position(), 0);
+    assert_eq!(source.take_bytes(1).unwrap(), Cow::<[u8]>::Owned(vec![1]));
+    assert_eq!(source.get_ref().position(), 1);
+    assert_eq!(
+        source.take_bytes(2).unwrap(),
+        Cow::<[u8]>::Owned(vec![2, 3])
+    );
+    assert_eq!(source.get_ref().position(), 3);
+    assert_eq!(
+        source.take_bytes(3).unwrap(),
+        Cow::<[u8]>::Owned(vec![4, 5, 6])
+    );
+    assert_eq!(source.get_ref().position(), 6);
+
+    let err = source.take_bytes(4).unwrap_err();
+    assert_error!(err, Error::Eof(|cause| cause_is_eof(&cause)));
+}
+
+#[test]
+fn take_bytes_hostile() {
+    let mut source = ReadSource::new(Cursor::new([1, 2, 3]));
+
+    let err = source.take_bytes(1 << 40).unwrap_err();
+    assert_error!(err, Error::Eof(|cause| cause_is_eof(&cause)));
+}
+
+#[test]
+fn take_bytes_to() {
+    let mut source = ReadSource::new(Cursor::new([1, 2, 3, 4, 5, 6, 7, 8, 9]));
+
+    let mut buf = [];
+    source.take_bytes_to(&mut buf).unwrap();
+    assert_eq!(source.get_ref().position(), 0);
+
+    let mut buf = [0; 1];
+    source.take_bytes_to(&mut buf).unwrap();
+    assert_eq!(buf, [1]);
+    assert_eq!(source.get_ref().position(), 1);
+
+    let mut buf = [0; 2];
+    source.take_bytes_to(&mut buf).unwrap();
+    assert_eq!(buf, [2, 3]);
+    assert_eq!(source.get_ref().position(), 3);
+
+    let mut buf = [0; 3];
+    source.take_bytes_to(&mut buf).unwrap();
+    assert_eq!(buf, [4, 5, 6]);
+    assert_eq!(source.get_ref().position(), 6);
+
+    let mut buf = [0; 4];
+    let err = source.take_bytes_to(&mut buf).unwrap_err();
+    assert_error!(err, Error::Eof(|cause| cause_is_eof(&cause)));
+}
+
+#[test]
+fn skip() {
+    let mut source = ReadSource::new(Cursor::new(vec![7; 1030]));
+
+    source.skip(0).unwrap();
+    assert_eq!(source.get_ref().position(), 0);
+
+    source.skip(1025).unwrap();
+    assert_eq!(source.get_ref().position(), 1025);
+
+    let err = source.skip(6).unwrap_err();
+    assert_error!(err, Error::Eof(|cause| cause_is_eof(&cause)));
+}
+
+#[test]
+fn skip_hostile() {
+    let mut source = ReadSource::new(Cursor::new([1, 2, 3]));
+
+    let err = source.skip(1 << 40).unwrap_err();
+    assert_error!(err, Error::Eof(|cause| cause_is_eof(&cause)));
+}
+
+#[test]
+fn into_inner() {
+    let mut source = ReadSource::new(Cursor::new([1, 2, 3]));
+
+    source.take_bytes(2).unwrap();
+
+    let cursor = source.into_inner();
+    assert_eq!(cursor.position(), 2);
+}
+
+#[test]
+fn remaining() {
+    let mut source = ReadSource::new(Cursor::new([1, 2, 3]));
+
+    assert_eq!(source.remaining().unwrap(), 1);
+    assert_eq!(source.remaining().unwrap(), 1);
+    assert_eq!(source.get_ref().position(), 1);
+    assert_eq!(
+        source.take_bytes(2).unwrap(),
+        Cow::<[u8]>::Owned(vec![1, 2])
+    );
+
+    assert_eq!(source.remaining().unwrap(), 1);
+    let mut buf = [0; 1];
+    source.take_bytes_to(&mut buf).unwrap();
+    assert_eq!(buf, [3]);
+
+    assert_eq!(source.remaining().unwrap(), 0);
+    assert_eq!(source.take_bytes(0).unwrap(), Cow::<[u8]>::Owned(vec![]));
+}
+
+#[test]
+fn remaining_skip() {
+    let mut source = ReadSource::new(Cursor::new([1, 2, 3]));
+
+    assert_eq!(source.remaining().unwrap(), 1);
+    source.skip(2).unwrap();
+    assert_eq!(source.take_bytes(1).unwrap(), Cow::<[u8]>::Owned(vec![3]));
+}
+
+#[test]
+fn remaining_unbounded() {
+    let mut source = ReadSource::new(io::repeat(1));
+
+    assert_eq!(source.remaining().unwrap(), 1);
+}
@@ -73,3 +73,29 @@ fn take_bytes_to() {
     assert_eq!(buf, [0, 0, 0, 0]);
     assert_eq!(source, [7, 8, 9]);
 }
+
+#[test]
+fn skip() {
+    let mut source = [1, 2, 3, 4].as_slice();
+
+    source.skip(0).unwrap();
+    assert_eq!(source, [1, 2, 3, 4]);
+
+    source.skip(3).unwrap();
+    assert_eq!(source, [4]);
+
+    let err = source.skip(2).unwrap_err();
+    assert_error!(err, Error::Eof(|cause| cause.is_none()));
+    assert_eq!(source, [4]);
+}
+
+#[test]
+fn remaining() {
+    let mut source = [1, 2, 3].as_slice();
+
+    assert_eq!(source.remaining().unwrap(), 3);
+    source.take_bytes(2).unwrap();
+    assert_eq!(source.remaining().unwrap(), 1);
+    source.take_bytes(1).unwrap();
+    assert_eq!(source.remaining().unwrap(), 0);
+}
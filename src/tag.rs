@@ -0,0 +1,83 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+/// The type tag, which precedes every value in the self-describing mode.
+///
+/// See [`Options::with_self_describing`](crate::Options::with_self_describing)
+/// for more information.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TypeTag {
+    Unit = 0,
+    Bool = 1,
+    I8 = 2,
+    I16 = 3,
+    I32 = 4,
+    I64 = 5,
+    I128 = 6,
+    U8 = 7,
+    U16 = 8,
+    U32 = 9,
+    U64 = 10,
+    U128 = 11,
+    F32 = 12,
+    F64 = 13,
+    Char = 14,
+    Str = 15,
+    Bytes = 16,
+    None = 17,
+    Some = 18,
+    Seq = 19,
+    Map = 20,
+}
+
+impl TypeTag {
+    const ALL: [TypeTag; 21] = [
+        TypeTag::Unit,
+        TypeTag::Bool,
+        TypeTag::I8,
+        TypeTag::I16,
+        TypeTag::I32,
+        TypeTag::I64,
+        TypeTag::I128,
+        TypeTag::U8,
+        TypeTag::U16,
+        TypeTag::U32,
+        TypeTag::U64,
+        TypeTag::U128,
+        TypeTag::F32,
+        TypeTag::F64,
+        TypeTag::Char,
+        TypeTag::Str,
+        TypeTag::Bytes,
+        TypeTag::None,
+        TypeTag::Some,
+        TypeTag::Seq,
+        TypeTag::Map,
+    ];
+
+    /// Returns the tag for the given `u8` value.
+    ///
+    /// Returns `None`, if `n` is not a type tag.
+    pub(crate) fn from_u8(n: u8) -> Option<TypeTag> {
+        Self::ALL.get(n as usize).copied()
+    }
+}
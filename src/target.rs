@@ -23,6 +23,7 @@
 #[cfg(test)]
 mod tests;
 
+use std::io::Write;
 use std::mem;
 
 use crate::error::{Error, Result};
@@ -40,6 +41,25 @@ pub trait PutBytes {
     /// If not all data could be written, an [`Error::NoSpace`] error should be
     /// returned.
     fn put_bytes(&mut self, buf: &[u8]) -> Result<()>;
+
+    /// Returns the current position of the target, if it supports patching.
+    ///
+    /// A target that supports patching can overwrite already written bytes
+    /// with [`PutBytes::patch_bytes`]. The [`Writer`] uses it to fill in the
+    /// length of a seq or map, which is not known in advance. If patching is
+    /// not supported (the default), `None` is returned and the [`Writer`]
+    /// buffers such a seq or map instead.
+    fn patch_position(&self) -> Option<usize> {
+        None
+    }
+
+    /// Overwrites the already written bytes at position `pos` with `buf`.
+    ///
+    /// The position was returned by [`PutBytes::patch_position`] before. The
+    /// default implementation returns an [`Error::Unsupported`] error.
+    fn patch_bytes(&mut self, _pos: usize, _buf: &[u8]) -> Result<()> {
+        Err(Error::Unsupported("patch_bytes"))
+    }
 }
 
 /// `PutBytes` is implemented for `&mut [u8]` by copying into the slice,
@@ -50,10 +70,16 @@ pub trait PutBytes {
 ///
 /// If the number of bytes to be written exceeds the size of the slice, the
 /// operation will return an [`Error::NoSpace`] error.
+///
+/// Patching is not supported, because the already written part of the slice
+/// is not accessible anymore. A [`Writer`] buffers a seq or map of unknown
+/// length instead.
+///
+/// [`Writer`]: crate::Writer
 impl PutBytes for &mut [u8] {
     fn put_bytes(&mut self, buf: &[u8]) -> Result<()> {
         if self.len() >= buf.len() {
-            let (a, b) = mem::replace(self, &mut []).split_at_mut(buf.len());
+            let (a, b) = mem::take(self).split_at_mut(buf.len());
 
             a.copy_from_slice(buf);
             *self = b;
@@ -66,8 +92,122 @@ impl PutBytes for &mut [u8] {
 }
 
 /// `PutBytes` is implemented for `Vec<u8>` by appending bytes to the `Vec`.
+///
+/// Patching is supported, the position is the length of the `Vec`.
 impl PutBytes for Vec<u8> {
     fn put_bytes(&mut self, buf: &[u8]) -> Result<()> {
-        Ok(self.extend_from_slice(buf))
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn patch_position(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn patch_bytes(&mut self, pos: usize, buf: &[u8]) -> Result<()> {
+        match pos
+            .checked_add(buf.len())
+            .and_then(|end| self.get_mut(pos..end))
+        {
+            Some(dst) => {
+                dst.copy_from_slice(buf);
+                Ok(())
+            }
+            None => Err(Error::NoSpace(None)),
+        }
+    }
+}
+
+/// A [`PutBytes`] implementation that discards all data.
+///
+/// Only the number of bytes is counted.
+#[derive(Debug, Default)]
+pub(crate) struct SizeTarget {
+    size: usize,
+}
+
+impl SizeTarget {
+    /// Returns the number of bytes put into the target.
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl PutBytes for SizeTarget {
+    fn put_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        self.size += buf.len();
+        Ok(())
+    }
+
+    fn patch_position(&self) -> Option<usize> {
+        Some(self.size)
+    }
+
+    fn patch_bytes(&mut self, _pos: usize, _buf: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A [`PutBytes`] implementation that puts bytes into an [`io::Write`]
+/// instance.
+///
+/// All data are written with [`Write::write_all`]. If the writer is not able
+/// to accept all data, an [`Error::NoSpace`] error is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use nuts_bytes::{WriteTarget, Writer};
+///
+/// let mut writer = Writer::new(WriteTarget::new(vec![]));
+/// let n = writer.serialize(&666u32).unwrap();
+///
+/// let mut target = writer.into_target();
+/// target.flush().unwrap();
+///
+/// assert_eq!(n, 4);
+/// assert_eq!(target.into_inner(), [0x00, 0x00, 0x02, 0x9A]);
+/// ```
+///
+/// [`io::Write`]: std::io::Write
+#[derive(Debug)]
+pub struct WriteTarget<W> {
+    inner: W,
+}
+
+impl<W: Write> WriteTarget<W> {
+    /// Creates a new `WriteTarget` instance, which puts bytes into the given
+    /// `inner` writer.
+    pub fn new(inner: W) -> WriteTarget<W> {
+        WriteTarget { inner }
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.inner.flush()?)
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `WriteTarget`, returning the underlying writer.
+    ///
+    /// Note that the writer is not flushed. Use [`WriteTarget::flush`] to
+    /// flush the writer before calling this function.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> PutBytes for WriteTarget<W> {
+    fn put_bytes(&mut self, buf: &[u8]) -> Result<()> {
+        Ok(self.inner.write_all(buf)?)
     }
 }
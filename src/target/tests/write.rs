@@ -0,0 +1,96 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use std::io::{self, ErrorKind, Write};
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::target::{PutBytes, WriteTarget};
+
+fn cause_is_write_zero(cause: &Option<Box<dyn std::error::Error + Send + Sync>>) -> bool {
+    cause
+        .as_ref()
+        .and_then(|err| err.downcast_ref::<io::Error>())
+        .is_some_and(|err| err.kind() == ErrorKind::WriteZero)
+}
+
+#[derive(Default)]
+struct Flushable {
+    buf: Vec<u8>,
+    flushed: Vec<u8>,
+}
+
+impl Write for Flushable {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed.append(&mut self.buf);
+        Ok(())
+    }
+}
+
+#[test]
+fn put() {
+    let mut target = WriteTarget::new(vec![]);
+
+    target.put_bytes(&[]).unwrap();
+    assert_eq!(*target.get_ref(), []);
+
+    target.put_bytes(&[1]).unwrap();
+    assert_eq!(*target.get_ref(), [1]);
+
+    target.put_bytes(&[2, 3]).unwrap();
+    assert_eq!(*target.get_ref(), [1, 2, 3]);
+
+    assert_eq!(target.into_inner(), [1, 2, 3]);
+}
+
+#[test]
+fn put_nospace() {
+    let mut buf = [b'x'; 3];
+
+    {
+        let mut target = WriteTarget::new(buf.as_mut_slice());
+
+        target.put_bytes(&[1, 2]).unwrap();
+
+        let err = target.put_bytes(&[3, 4]).unwrap_err();
+        assert_error!(err, Error::NoSpace(|cause| cause_is_write_zero(&cause)));
+    }
+
+    assert_eq!(buf, [1, 2, 3]);
+}
+
+#[test]
+fn flush() {
+    let mut target = WriteTarget::new(Flushable::default());
+
+    target.put_bytes(&[1, 2, 3]).unwrap();
+    assert_eq!(target.get_ref().buf, [1, 2, 3]);
+    assert_eq!(target.get_ref().flushed, []);
+
+    target.flush().unwrap();
+    assert_eq!(target.get_ref().buf, []);
+    assert_eq!(target.get_ref().flushed, [1, 2, 3]);
+}
@@ -24,8 +24,11 @@
 mod tests;
 
 use serde::{ser, Serialize};
+use std::io::Write;
 
 use crate::error::{Error, Result};
+use crate::options::{Endian, Fields, Int, Options, Width};
+use crate::tag::TypeTag;
 use crate::target::PutBytes;
 
 /// A cursor like utility that writes structured data into an arbitrary target.
@@ -35,14 +38,54 @@ use crate::target::PutBytes;
 #[derive(Debug)]
 pub struct Writer<T> {
     target: T,
+    options: Options,
+    position: usize,
+    depth: usize,
+    buffers: Vec<Vec<u8>>,
+}
+
+macro_rules! write_fixed {
+    ($writer:ident, $value:expr) => {{
+        let value = $value;
+        let bytes = match $writer.options.endian {
+            Endian::Big => value.to_be_bytes(),
+            Endian::Little => value.to_le_bytes(),
+        };
+
+        $writer.write_bytes(&bytes)
+    }};
 }
 
 macro_rules! write_primitive {
     ($(#[$outer:meta])* $name:ident -> $ty:ty) => {
         $(#[$outer])*
         pub fn $name(&mut self, value: $ty) -> Result<usize> {
-            const N: usize = std::mem::size_of::<$ty>();
-            self.target.put_bytes(&value.to_be_bytes()).map(|()| N)
+            write_fixed!(self, value)
+        }
+    };
+}
+
+macro_rules! write_integer {
+    ($(#[$outer:meta])* $name:ident -> $ty:ty) => {
+        $(#[$outer])*
+        pub fn $name(&mut self, value: $ty) -> Result<usize> {
+            match self.options.int {
+                Int::Varint => self.write_varint(value as u128),
+                Int::Fixed | Int::VarintPrefixes => write_fixed!(self, value),
+            }
+        }
+    };
+
+    ($(#[$outer:meta])* $name:ident -> $ty:ty, zigzag $uty:ty) => {
+        $(#[$outer])*
+        pub fn $name(&mut self, value: $ty) -> Result<usize> {
+            match self.options.int {
+                Int::Varint => {
+                    let n = ((value << 1) ^ (value >> (<$ty>::BITS - 1))) as $uty;
+                    self.write_varint(n as u128)
+                }
+                Int::Fixed | Int::VarintPrefixes => write_fixed!(self, value),
+            }
         }
     };
 }
@@ -53,8 +96,21 @@ impl<T: PutBytes> Writer<T> {
     /// The target, where the writer puts the binary data, is passed to the
     /// function. Every type, that implements the [`PutBytes`] trait can be the
     /// target of this writer.
+    ///
+    /// The writer uses the default [`Options`]. Use [`Options::build_writer`]
+    /// to create a writer with custom options.
     pub fn new(target: T) -> Writer<T> {
-        Writer { target }
+        Self::with_options(target, Options::new())
+    }
+
+    pub(crate) fn with_options(target: T, options: Options) -> Writer<T> {
+        Writer {
+            target,
+            options,
+            position: 0,
+            depth: 0,
+            buffers: vec![],
+        }
     }
 
     /// Serializes a data structure that implements
@@ -85,52 +141,336 @@ impl<T: PutBytes> Writer<T> {
         write_u8 -> u8
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `i16` value at the end of this writer.
-        write_i16 -> i16
+        write_i16 -> i16, zigzag u16
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `u16` value at the end of this writer.
         write_u16 -> u16
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `i32` value at the end of this writer.
-        write_i32 -> i32
+        write_i32 -> i32, zigzag u32
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `u32` value at the end of this writer.
         write_u32 -> u32
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `i64` value at the end of this writer.
-        write_i64 -> i64
+        write_i64 -> i64, zigzag u64
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `u64` value at the end of this writer.
         write_u64 -> u64
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `i128` value at the end of this writer.
-        write_i128 -> i128
+        write_i128 -> i128, zigzag u128
     );
 
-    write_primitive!(
+    write_integer!(
         /// Appends an `u128` value at the end of this writer.
         write_u128 -> u128
     );
 
+    write_primitive!(
+        /// Appends an `f32` value at the end of this writer.
+        write_f32 -> f32
+    );
+
+    write_primitive!(
+        /// Appends an `f64` value at the end of this writer.
+        write_f64 -> f64
+    );
+
+    /// Appends a varint encoded integer at the end of this writer.
+    fn write_varint(&mut self, mut value: u128) -> Result<usize> {
+        let mut buf = [0; 19];
+        let mut n = 0;
+
+        loop {
+            buf[n] = (value & 0x7F) as u8;
+            value >>= 7;
+
+            if value == 0 {
+                n += 1;
+                break;
+            }
+
+            buf[n] |= 0x80;
+            n += 1;
+        }
+
+        self.write_bytes(&buf[..n])
+    }
+
+    /// Appends an integer prefix of the given `width`.
+    ///
+    /// The caller must ensure that `n` fits into `width`.
+    fn write_prefix(&mut self, width: Width, n: u64) -> Result<usize> {
+        match self.options.int {
+            Int::Fixed => match width {
+                Width::U8 => write_fixed!(self, n as u8),
+                Width::U16 => write_fixed!(self, n as u16),
+                Width::U32 => write_fixed!(self, n as u32),
+                Width::U64 => write_fixed!(self, n),
+            },
+            Int::VarintPrefixes | Int::Varint => self.write_varint(n as u128),
+        }
+    }
+
+    /// Appends the length of a string, byte array, seq or map.
+    fn write_len(&mut self, len: usize) -> Result<usize> {
+        let len = len as u64;
+        let width = self.options.len_width;
+
+        if len <= width.max() {
+            self.write_prefix(width, len)
+        } else {
+            Err(Error::LengthOverflow(len))
+        }
+    }
+
+    /// Appends the index of an enum variant.
+    fn write_tag(&mut self, index: u32) -> Result<usize> {
+        let width = self.options.tag_width;
+
+        if index as u64 <= width.max() {
+            self.write_prefix(width, index as u64)
+        } else {
+            Err(Error::TagOverflow(index))
+        }
+    }
+
+    /// Appends the type tag of the next value (self-describing mode).
+    ///
+    /// Nothing is written in compact mode.
+    fn write_type(&mut self, tag: TypeTag) -> Result<usize> {
+        if self.options.self_describing {
+            self.write_u8(tag as u8)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Appends the type tag of the next value followed by the value itself,
+    /// which is written by `f`.
+    fn write_typed<F: FnOnce(&mut Self) -> Result<usize>>(
+        &mut self,
+        tag: TypeTag,
+        f: F,
+    ) -> Result<usize> {
+        self.write_type(tag).and_then(|a| f(self).map(|b| a + b))
+    }
+
+    /// Appends the beginning of an enum variant.
+    ///
+    /// The variant is identified either by its `index` or by its `name`.
+    /// In self-describing mode the variant is encoded as a map with a single
+    /// entry, the payload is the value of the entry.
+    fn write_variant(&mut self, index: u32, name: &'static str) -> Result<usize> {
+        if self.options.self_describing {
+            let n = self.write_type(TypeTag::Map)? + self.write_len(1)?;
+
+            if self.options.variant_names {
+                name.serialize(self).map(|m| n + m)
+            } else {
+                // serde accepts only an u64 index as identifier of buffered content
+                (index as u64).serialize(self).map(|m| n + m)
+            }
+        } else if self.options.variant_names {
+            self.write_prefixed(name.as_bytes())
+        } else {
+            self.write_tag(index)
+        }
+    }
+
+    /// Appends the length of `bytes` followed by `bytes` itself.
+    fn write_prefixed(&mut self, bytes: &[u8]) -> Result<usize> {
+        self.write_len(bytes.len())
+            .and_then(|a| self.write_bytes(bytes).map(|b| a + b))
+    }
+
     /// Appends the given `bytes` at the end of this writer.
     pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<usize> {
-        self.target.put_bytes(bytes).map(|()| bytes.len())
+        self.put_bytes(bytes)?;
+
+        self.position += bytes.len();
+        Ok(bytes.len())
+    }
+
+    /// Puts `bytes` into the innermost buffer or, if nothing is buffered,
+    /// into the target.
+    fn put_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        match self.buffers.last_mut() {
+            Some(buf) => {
+                buf.extend_from_slice(bytes);
+                Ok(())
+            }
+            None => self.target.put_bytes(bytes),
+        }
+    }
+
+    /// Returns the position for [`Writer::patch_bytes`], if patching is
+    /// supported.
+    fn patch_position(&self) -> Option<usize> {
+        match self.buffers.last() {
+            Some(buf) => Some(buf.len()),
+            None => self.target.patch_position(),
+        }
+    }
+
+    /// Overwrites already written bytes at position `pos`.
+    fn patch_bytes(&mut self, pos: usize, bytes: &[u8]) -> Result<()> {
+        match self.buffers.last_mut() {
+            Some(buf) => {
+                buf[pos..pos + bytes.len()].copy_from_slice(bytes);
+                Ok(())
+            }
+            None => self.target.patch_bytes(pos, bytes),
+        }
+    }
+
+    /// Starts a value, whose length is not known in advance.
+    ///
+    /// With fixed size integers and a target that supports patching, a
+    /// placeholder is written, which is patched at the end. Otherwise the
+    /// elements are buffered.
+    fn begin_unknown_len(&mut self) -> Result<(usize, Pending)> {
+        if let Int::Fixed = self.options.int {
+            if let Some(pos) = self.patch_position() {
+                let n = self.write_prefix(self.options.len_width, 0)?;
+                return Ok((n, Pending::Patch(pos)));
+            }
+        }
+
+        self.buffers.push(vec![]);
+        Ok((0, Pending::Buffer(self.position)))
+    }
+
+    /// Finishes a value started with [`Writer::begin_unknown_len`].
+    fn end_unknown_len(&mut self, pending: Pending, len: usize) -> Result<usize> {
+        match pending {
+            Pending::Patch(pos) => {
+                let mut prefix = Writer::with_options(vec![], self.options);
+                prefix.write_len(len)?;
+
+                self.patch_bytes(pos, &prefix.into_target()).map(|()| 0)
+            }
+            Pending::Buffer(_) => {
+                let buf = self.buffers.pop().unwrap_or_default();
+                let n = self.write_len(len)?;
+
+                self.put_bytes(&buf).map(|()| n)
+            }
+        }
+    }
+
+    /// Appends a value prefixed with its length in bytes.
+    ///
+    /// The value is written by `f`.
+    fn write_delimited<F: FnOnce(&mut Self) -> Result<usize>>(&mut self, f: F) -> Result<usize> {
+        let (a, pending) = self.begin_unknown_len()?;
+
+        match f(self) {
+            Ok(b) => self.end_unknown_len(pending, b).map(|c| a + b + c),
+            Err(err) => {
+                self.abort_unknown_len(pending);
+                Err(err)
+            }
+        }
+    }
+
+    /// Appends the payload of an enum variant, which is written by `f`.
+    ///
+    /// The payload is wrapped into an envelope, if enabled.
+    fn write_payload<F: FnOnce(&mut Self) -> Result<usize>>(&mut self, f: F) -> Result<usize> {
+        if self.options.enum_envelope() {
+            self.write_delimited(f)
+        } else {
+            f(self)
+        }
+    }
+
+    /// Appends the value of a tagged struct field.
+    ///
+    /// In compact mode the value is prefixed with its length, thus it can be
+    /// skipped by the reader.
+    fn write_field_value<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<usize> {
+        if self.options.self_describing {
+            value.serialize(self)
+        } else {
+            self.write_delimited(|writer| value.serialize(writer))
+        }
+    }
+
+    /// Returns the position of the writer.
+    ///
+    /// This is the number of bytes written into the target so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Runs `f` one nesting level deeper.
+    fn nested<F: FnOnce(&mut Self) -> Result<usize>>(&mut self, f: F) -> Result<usize> {
+        self.enter()?;
+        let result = f(self);
+        self.leave();
+
+        result
     }
 }
 
+impl<T> Writer<T> {
+    /// Enters the next nesting level.
+    ///
+    /// Fails with an [`Error::DepthExceeded`] error, if the configured maximum
+    /// depth is exceeded.
+    fn enter(&mut self) -> Result<()> {
+        match self.options.max_depth {
+            Some(max) if self.depth >= max => Err(Error::DepthExceeded(max)),
+            _ => {
+                self.depth += 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Leaves the current nesting level.
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    /// Aborts a value started with [`Writer::begin_unknown_len`].
+    ///
+    /// The buffered elements are discarded and the position is reset to the
+    /// beginning of the value.
+    fn abort_unknown_len(&mut self, pending: Pending) {
+        if let Pending::Buffer(position) = pending {
+            self.buffers.pop();
+            self.position = position;
+        }
+    }
+}
+
+/// A value, whose length is not known in advance.
+enum Pending {
+    /// A placeholder was written at the given position.
+    Patch(usize),
+
+    /// The elements are buffered, the value started at the given position of
+    /// the writer.
+    Buffer(usize),
+}
+
 impl<T> AsRef<T> for Writer<T> {
     fn as_ref(&self) -> &T {
         &self.target
@@ -149,86 +489,105 @@ impl<'a, P: PutBytes> ser::Serializer for &'a mut Writer<P> {
     type SerializeStructVariant = StateSerializer<'a, P>;
 
     fn serialize_bool(self, v: bool) -> Result<usize> {
-        self.write_u8(if v { 1 } else { 0 })
+        self.write_typed(TypeTag::Bool, |w| w.write_u8(if v { 1 } else { 0 }))
     }
 
     fn serialize_i8(self, v: i8) -> Result<usize> {
-        self.write_i8(v)
+        self.write_typed(TypeTag::I8, |w| w.write_i8(v))
     }
 
     fn serialize_i16(self, v: i16) -> Result<usize> {
-        self.write_i16(v)
+        self.write_typed(TypeTag::I16, |w| w.write_i16(v))
     }
 
     fn serialize_i32(self, v: i32) -> Result<usize> {
-        self.write_i32(v)
+        self.write_typed(TypeTag::I32, |w| w.write_i32(v))
     }
 
     fn serialize_i64(self, v: i64) -> Result<usize> {
-        self.write_i64(v)
+        self.write_typed(TypeTag::I64, |w| w.write_i64(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<usize> {
+        self.write_typed(TypeTag::I128, |w| w.write_i128(v))
     }
 
     fn serialize_u8(self, v: u8) -> Result<usize> {
-        self.write_u8(v)
+        self.write_typed(TypeTag::U8, |w| w.write_u8(v))
     }
 
     fn serialize_u16(self, v: u16) -> Result<usize> {
-        self.write_u16(v)
+        self.write_typed(TypeTag::U16, |w| w.write_u16(v))
     }
 
     fn serialize_u32(self, v: u32) -> Result<usize> {
-        self.write_u32(v)
+        self.write_typed(TypeTag::U32, |w| w.write_u32(v))
     }
 
     fn serialize_u64(self, v: u64) -> Result<usize> {
-        self.write_u64(v)
+        self.write_typed(TypeTag::U64, |w| w.write_u64(v))
     }
 
-    fn serialize_f32(self, _v: f32) -> Result<usize> {
-        unimplemented!()
+    fn serialize_u128(self, v: u128) -> Result<usize> {
+        self.write_typed(TypeTag::U128, |w| w.write_u128(v))
     }
 
-    fn serialize_f64(self, _v: f64) -> Result<usize> {
-        unimplemented!()
+    fn serialize_f32(self, v: f32) -> Result<usize> {
+        self.write_typed(TypeTag::F32, |w| w.write_f32(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<usize> {
+        self.write_typed(TypeTag::F64, |w| w.write_f64(v))
     }
 
     fn serialize_char(self, v: char) -> Result<usize> {
-        self.write_u32(v as u32)
+        self.write_typed(TypeTag::Char, |w| w.write_u32(v as u32))
     }
 
     fn serialize_str(self, v: &str) -> Result<usize> {
-        self.serialize_bytes(v.as_bytes())
+        self.write_typed(TypeTag::Str, |w| w.write_prefixed(v.as_bytes()))
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<usize> {
-        self.write_u64(v.len() as u64)
-            .and_then(|a| self.write_bytes(v).map(|b| a + b))
+        self.write_typed(TypeTag::Bytes, |w| w.write_prefixed(v))
     }
 
     fn serialize_none(self) -> Result<usize> {
-        self.write_u8(0)
+        if self.options.self_describing {
+            self.write_type(TypeTag::None)
+        } else {
+            self.write_u8(0)
+        }
     }
 
     fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<usize> {
-        self.write_u8(1)
-            .and_then(|a| value.serialize(self).map(|b| a + b))
+        let a = if self.options.self_describing {
+            self.write_type(TypeTag::Some)?
+        } else {
+            self.write_u8(1)?
+        };
+
+        self.nested(|writer| value.serialize(writer)).map(|b| a + b)
     }
 
     fn serialize_unit(self) -> Result<usize> {
-        Ok(0)
+        self.write_type(TypeTag::Unit)
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<usize> {
-        Ok(0)
+        self.write_type(TypeTag::Unit)
     }
 
     fn serialize_unit_variant(
         self,
         _name: &'static str,
         variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
     ) -> Result<usize> {
-        self.write_u32(variant_index)
+        self.write_variant(variant_index, variant).and_then(|a| {
+            self.write_payload(|writer| writer.write_type(TypeTag::Unit))
+                .map(|b| a + b)
+        })
     }
 
     fn serialize_newtype_struct<T: Serialize + ?Sized>(
@@ -236,79 +595,241 @@ impl<'a, P: PutBytes> ser::Serializer for &'a mut Writer<P> {
         _name: &'static str,
         value: &T,
     ) -> Result<usize> {
-        value.serialize(self)
+        self.nested(|writer| value.serialize(writer))
     }
 
     fn serialize_newtype_variant<T: Serialize + ?Sized>(
         self,
         _name: &'static str,
         variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         value: &T,
     ) -> Result<usize> {
-        self.write_u32(variant_index)
-            .and_then(|a| value.serialize(self).map(|b| a + b))
+        self.write_variant(variant_index, variant).and_then(|a| {
+            self.write_payload(|writer| writer.nested(|writer| value.serialize(writer)))
+                .map(|b| a + b)
+        })
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
-        len.ok_or(Error::RequiredLength)
-            .and_then(|len| self.write_u64(len as u64))
-            .map(move |n| StateSerializer::new(self, n))
+        self.write_type(TypeTag::Seq)
+            .and_then(move |n| StateSerializer::new(self, n, false))
+            .and_then(|state| state.with_len(len))
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
-        Ok(StateSerializer::new(self, 0))
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        StateSerializer::new(self, 0, false).and_then(|state| state.fixed(len))
     }
 
     fn serialize_tuple_struct(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        Ok(StateSerializer::new(self, 0))
+        StateSerializer::new(self, 0, false).and_then(|state| state.fixed(len))
     }
 
     fn serialize_tuple_variant(
         self,
         _name: &'static str,
         variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
+        variant: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
-        self.write_u32(variant_index)
-            .map(move |n| StateSerializer::new(self, n))
+        let envelope = self.options.enum_envelope();
+
+        self.write_variant(variant_index, variant)
+            .and_then(move |n| StateSerializer::new(self, n, envelope))
+            .and_then(|state| state.fixed(len))
     }
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
-        len.ok_or(Error::RequiredLength)
-            .and_then(|len| self.write_u64(len as u64))
-            .map(move |n| StateSerializer::new(self, n))
+        self.write_type(TypeTag::Map)
+            .and_then(move |n| StateSerializer::new(self, n, false))
+            .and_then(|state| state.with_len(len))
     }
 
-    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
-        Ok(StateSerializer::new(self, 0))
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        let envelope = self.options.struct_envelope();
+        StateSerializer::new(self, 0, envelope).and_then(|state| state.fields(len))
     }
 
     fn serialize_struct_variant(
         self,
         _name: &'static str,
         variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
+        variant: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        self.write_u32(variant_index)
-            .map(move |n| StateSerializer::new(self, n))
+        let envelope = self.options.enum_envelope();
+
+        self.write_variant(variant_index, variant)
+            .and_then(move |n| StateSerializer::new(self, n, envelope))
+            .and_then(|state| state.fields(len))
     }
 }
 
+/// Serializes the given `value` into a [`Vec`].
+///
+/// The default [`Options`] are used. Use [`Options::to_vec`] to customize
+/// the format.
+pub fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    Options::new().to_vec(value)
+}
+
+/// Serializes the given `value` into the given [`io::Write`] instance.
+///
+/// On success the number of bytes written is returned. Note that `writer` is
+/// not flushed.
+///
+/// The default [`Options`] are used. Use [`Options::to_writer`] to customize
+/// the format.
+///
+/// [`io::Write`]: std::io::Write
+pub fn to_writer<W: Write, T: Serialize>(writer: W, value: &T) -> Result<usize> {
+    Options::new().to_writer(writer, value)
+}
+
+/// Calculates the number of bytes required to serialize the given `value`.
+///
+/// The serialization is performed, but the data are discarded. Use this
+/// function to allocate a buffer of the exact size before serializing into
+/// it.
+pub fn serialized_size<T: Serialize>(value: &T) -> Result<usize> {
+    Options::new().serialized_size(value)
+}
+
 pub struct StateSerializer<'a, T> {
     writer: &'a mut Writer<T>,
     ok: usize,
+    key: bool,
+    count: usize,
+    field: usize,
+    pending: Option<Pending>,
+    envelope: Option<(Pending, usize)>,
+}
+
+impl<'a, T: PutBytes> StateSerializer<'a, T> {
+    /// Creates a serializer for a compound value one nesting level deeper.
+    ///
+    /// The level is left again when the serializer is dropped. If `envelope`
+    /// is set, the value is wrapped into an envelope, which is closed by
+    /// [`StateSerializer::end_envelope`].
+    fn new(writer: &'a mut Writer<T>, ok: usize, envelope: bool) -> Result<StateSerializer<'a, T>> {
+        writer.enter()?;
+
+        let mut state = StateSerializer {
+            writer,
+            ok,
+            key: false,
+            count: 0,
+            field: 0,
+            pending: None,
+            envelope: None,
+        };
+
+        if envelope {
+            let (n, pending) = state.writer.begin_unknown_len()?;
+
+            state.ok += n;
+            state.envelope = Some((pending, state.ok));
+        }
+
+        Ok(state)
+    }
+
+    /// Writes the length of a seq or map.
+    ///
+    /// If the length is not known in advance, it is filled in by
+    /// [`StateSerializer::end_len`].
+    fn with_len(mut self, len: Option<usize>) -> Result<StateSerializer<'a, T>> {
+        match len {
+            Some(len) => self.ok += self.writer.write_len(len)?,
+            None => {
+                let (n, pending) = self.writer.begin_unknown_len()?;
+
+                self.ok += n;
+                self.pending = Some(pending);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Prepares a tuple or struct with `len` elements.
+    ///
+    /// The length is only written in self-describing mode.
+    fn fixed(mut self, len: usize) -> Result<StateSerializer<'a, T>> {
+        if self.writer.options.self_describing {
+            self.ok += self.writer.write_type(TypeTag::Seq)?;
+            self.with_len(Some(len))
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Prepares a struct with `len` fields.
+    ///
+    /// Tagged fields are encoded like a map.
+    fn fields(mut self, len: usize) -> Result<StateSerializer<'a, T>> {
+        match self.writer.options.field_keys() {
+            Fields::Positional => self.fixed(len),
+            Fields::Names | Fields::Indices => {
+                self.ok += self.writer.write_type(TypeTag::Map)?;
+                self.with_len(Some(len))
+            }
+        }
+    }
+
+    /// Serializes the field `key` of a struct.
+    fn field<V: Serialize + ?Sized>(&mut self, key: &'static str, value: &V) -> Result<()> {
+        let writer = &mut *self.writer;
+
+        let n = match writer.options.field_keys() {
+            Fields::Positional => value.serialize(writer)?,
+            Fields::Names => key.serialize(&mut *writer)? + writer.write_field_value(value)?,
+            Fields::Indices => {
+                (self.field as u32).serialize(&mut *writer)? + writer.write_field_value(value)?
+            }
+        };
+
+        self.ok += n;
+        self.field += 1;
+
+        Ok(())
+    }
+
+    /// Finishes a seq or map, whose length was not known in advance.
+    fn end_len(&mut self) -> Result<()> {
+        if let Some(pending) = self.pending.take() {
+            self.ok += self.writer.end_unknown_len(pending, self.count)?;
+        }
+
+        Ok(())
+    }
+
+    /// Closes the envelope of the value, if any.
+    fn end_envelope(&mut self) -> Result<()> {
+        if let Some((pending, start)) = self.envelope.take() {
+            self.ok += self.writer.end_unknown_len(pending, self.ok - start)?;
+        }
+
+        Ok(())
+    }
 }
 
-impl<'a, T> StateSerializer<'a, T> {
-    fn new(writer: &'a mut Writer<T>, ok: usize) -> StateSerializer<T> {
-        StateSerializer { writer, ok }
+impl<'a, T> Drop for StateSerializer<'a, T> {
+    fn drop(&mut self) {
+        // serialization was aborted, discard the buffered elements
+        if let Some(pending) = self.pending.take() {
+            self.writer.abort_unknown_len(pending);
+        }
+
+        if let Some((pending, _)) = self.envelope.take() {
+            self.writer.abort_unknown_len(pending);
+        }
+
+        self.writer.leave();
     }
 }
 
@@ -319,12 +840,12 @@ impl<'a, P: PutBytes> ser::SerializeSeq for StateSerializer<'a, P> {
     fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
         value.serialize(&mut *self.writer).map(|n| {
             self.ok += n;
-            ()
+            self.count += 1;
         })
     }
 
-    fn end(self) -> Result<usize> {
-        Ok(self.ok)
+    fn end(mut self) -> Result<usize> {
+        self.end_len().map(|()| self.ok)
     }
 }
 
@@ -335,7 +856,6 @@ impl<'a, P: PutBytes> ser::SerializeTuple for StateSerializer<'a, P> {
     fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
         value.serialize(&mut *self.writer).map(|n| {
             self.ok += n;
-            ()
         })
     }
 
@@ -351,7 +871,6 @@ impl<'a, P: PutBytes> ser::SerializeTupleStruct for StateSerializer<'a, P> {
     fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
         value.serialize(&mut *self.writer).map(|n| {
             self.ok += n;
-            ()
         })
     }
 
@@ -367,12 +886,11 @@ impl<'a, P: PutBytes> ser::SerializeTupleVariant for StateSerializer<'a, P> {
     fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
         value.serialize(&mut *self.writer).map(|n| {
             self.ok += n;
-            ()
         })
     }
 
-    fn end(self) -> Result<usize> {
-        Ok(self.ok)
+    fn end(mut self) -> Result<usize> {
+        self.end_envelope().map(|()| self.ok)
     }
 }
 
@@ -380,30 +898,36 @@ impl<'a, P: PutBytes> ser::SerializeMap for StateSerializer<'a, P> {
     type Ok = usize;
     type Error = Error;
 
-    fn serialize_key<T: Serialize + ?Sized>(&mut self, _key: &T) -> Result<()> {
-        unimplemented!()
-    }
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        if self.key {
+            return Err(Error::MissingValue);
+        }
 
-    fn serialize_value<T: Serialize + ?Sized>(&mut self, _value: &T) -> Result<()> {
-        unimplemented!()
+        key.serialize(&mut *self.writer).map(|n| {
+            self.ok += n;
+            self.key = true;
+        })
     }
 
-    fn end(self) -> Result<usize> {
-        Ok(self.ok)
-    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        if !self.key {
+            return Err(Error::MissingKey);
+        }
 
-    fn serialize_entry<K: Serialize + ?Sized, V: Serialize + ?Sized>(
-        &mut self,
-        key: &K,
-        value: &V,
-    ) -> Result<()> {
-        key.serialize(&mut *self.writer).and_then(|a| {
-            value.serialize(&mut *self.writer).map(|b| {
-                self.ok += a + b;
-                ()
-            })
+        value.serialize(&mut *self.writer).map(|n| {
+            self.ok += n;
+            self.key = false;
+            self.count += 1;
         })
     }
+
+    fn end(mut self) -> Result<usize> {
+        if self.key {
+            Err(Error::MissingValue)
+        } else {
+            self.end_len().map(|()| self.ok)
+        }
+    }
 }
 
 impl<'a, P: PutBytes> ser::SerializeStruct for StateSerializer<'a, P> {
@@ -412,17 +936,19 @@ impl<'a, P: PutBytes> ser::SerializeStruct for StateSerializer<'a, P> {
 
     fn serialize_field<T: Serialize + ?Sized>(
         &mut self,
-        _key: &'static str,
+        key: &'static str,
         value: &T,
     ) -> Result<()> {
-        value.serialize(&mut *self.writer).map(|n| {
-            self.ok += n;
-            ()
-        })
+        self.field(key, value)
     }
 
-    fn end(self) -> Result<usize> {
-        Ok(self.ok)
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        self.field += 1;
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<usize> {
+        self.end_envelope().map(|()| self.ok)
     }
 }
 
@@ -432,16 +958,18 @@ impl<'a, P: PutBytes> ser::SerializeStructVariant for StateSerializer<'a, P> {
 
     fn serialize_field<T: Serialize + ?Sized>(
         &mut self,
-        _key: &'static str,
+        key: &'static str,
         value: &T,
     ) -> Result<()> {
-        value.serialize(&mut *self.writer).map(|n| {
-            self.ok += n;
-            ()
-        })
+        self.field(key, value)
     }
 
-    fn end(self) -> Result<usize> {
-        Ok(self.ok)
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        self.field += 1;
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<usize> {
+        self.end_envelope().map(|()| self.ok)
     }
 }
@@ -20,5 +20,29 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
 // IN THE SOFTWARE.
 
+mod depth;
+mod endian;
+mod envelope;
+mod fields;
+mod func;
+mod position;
+mod self_describing;
 mod ser;
+mod unknown_len;
+mod variant_names;
+mod varint;
+mod width;
 mod writer;
+
+use serde::Serialize;
+
+use crate::options::Options;
+
+fn serialize<T: Serialize>(options: Options, value: &T) -> Vec<u8> {
+    let mut writer = options.build_writer(vec![]);
+    let n = writer.serialize(value).unwrap();
+    let buf = writer.into_target();
+
+    assert_eq!(n, buf.len());
+    buf
+}
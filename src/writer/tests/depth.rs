@@ -0,0 +1,77 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::Options;
+
+#[derive(Serialize)]
+enum Node {
+    Leaf,
+    Next(Box<Node>),
+}
+
+fn nodes(n: usize) -> Node {
+    (0..n).fold(Node::Leaf, |node, _| Node::Next(Box::new(node)))
+}
+
+#[test]
+fn unlimited() {
+    let mut writer = Options::new().build_writer(vec![]);
+    assert_eq!(writer.serialize(&nodes(2)).unwrap(), 12);
+    assert_eq!(writer.into_target(), [0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0]);
+}
+
+#[test]
+fn enum_in_limit() {
+    let mut writer = Options::new().with_max_depth(3).build_writer(vec![]);
+    assert_eq!(writer.serialize(&nodes(3)).unwrap(), 16);
+}
+
+#[test]
+fn enum_exceeded() {
+    let mut writer = Options::new().with_max_depth(3).build_writer(vec![]);
+    let err = writer.serialize(&nodes(4)).unwrap_err();
+    assert_error!(err, Error::DepthExceeded(|n| n == 3));
+}
+
+#[test]
+fn seq() {
+    let mut writer = Options::new().with_max_depth(2).build_writer(vec![]);
+    assert_eq!(writer.serialize(&vec![vec![1u8]]).unwrap(), 17);
+
+    let mut writer = Options::new().with_max_depth(1).build_writer(vec![]);
+    let err = writer.serialize(&vec![vec![1u8]]).unwrap_err();
+    assert_error!(err, Error::DepthExceeded(|n| n == 1));
+}
+
+#[test]
+fn reuse_after_error() {
+    let mut writer = Options::new().with_max_depth(2).build_writer(vec![]);
+
+    let err = writer.serialize(&((Some(1u8),),)).unwrap_err();
+    assert_error!(err, Error::DepthExceeded(|n| n == 2));
+
+    assert_eq!(writer.serialize(&(Some(1u8), Some(2u8))).unwrap(), 4);
+}
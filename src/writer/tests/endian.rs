@@ -0,0 +1,97 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+
+use crate::options::{Endian, Options};
+
+#[test]
+fn primitives() {
+    let mut writer = Options::new().with_little_endian().build_writer(vec![]);
+
+    assert_eq!(writer.write_u16(4711).unwrap(), 2);
+    assert_eq!(writer.write_i32(666).unwrap(), 4);
+    assert_eq!(writer.write_f32(1.0).unwrap(), 4);
+    assert_eq!(writer.serialize(&'💯').unwrap(), 4);
+    assert_eq!(
+        writer.into_target(),
+        [0x67, 0x12, 0x9A, 0x02, 0x00, 0x00, 0x00, 0x00, 0x80, 0x3F, 0xAF, 0xF4, 0x01, 0x00]
+    );
+}
+
+#[test]
+fn big_endian() {
+    let mut writer = Options::new()
+        .with_little_endian()
+        .with_big_endian()
+        .build_writer(vec![]);
+
+    assert_eq!(writer.write_u16(4711).unwrap(), 2);
+    assert_eq!(writer.into_target(), [0x12, 0x67]);
+}
+
+#[test]
+fn with_endian() {
+    let mut writer = Options::new()
+        .with_endian(Endian::Little)
+        .build_writer(vec![]);
+
+    assert_eq!(writer.write_u16(4711).unwrap(), 2);
+    assert_eq!(writer.into_target(), [0x67, 0x12]);
+}
+
+#[test]
+fn str() {
+    let mut writer = Options::new().with_little_endian().build_writer(vec![]);
+
+    assert_eq!(writer.serialize(&"abc").unwrap(), 11);
+    assert_eq!(
+        writer.into_target(),
+        [0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'a', b'b', b'c']
+    );
+}
+
+#[test]
+fn vec() {
+    let mut writer = Options::new().with_little_endian().build_writer(vec![]);
+
+    assert_eq!(writer.serialize(&vec![1u16, 2]).unwrap(), 12);
+    assert_eq!(
+        writer.into_target(),
+        [0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00]
+    );
+}
+
+#[test]
+fn r#enum() {
+    #[derive(Serialize)]
+    enum Enum {
+        #[allow(dead_code)]
+        V1,
+        V2(u16),
+    }
+
+    let mut writer = Options::new().with_little_endian().build_writer(vec![]);
+
+    assert_eq!(writer.serialize(&Enum::V2(4711)).unwrap(), 6);
+    assert_eq!(writer.into_target(), [0x01, 0x00, 0x00, 0x00, 0x67, 0x12]);
+}
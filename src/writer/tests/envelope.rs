@@ -0,0 +1,163 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::options::{Envelope, Options};
+use crate::{assert_error, Width};
+
+#[derive(Serialize)]
+struct Sample {
+    a: u8,
+    b: u16,
+}
+
+#[derive(Serialize)]
+struct Outer {
+    x: u8,
+    inner: Sample,
+}
+
+#[derive(Serialize)]
+enum Enum {
+    A,
+    B(u8),
+    C(u8, u8),
+    D { x: u8 },
+}
+
+fn serialize<T: Serialize>(options: Options, value: &T) -> Vec<u8> {
+    super::serialize(options.with_length_width(Width::U8), value)
+}
+
+fn slice<T: Serialize>(options: Options, value: &T) -> Vec<u8> {
+    let mut buf = [0; 32];
+
+    let mut writer = options
+        .with_length_width(Width::U8)
+        .build_writer(buf.as_mut_slice());
+    let n = writer.serialize(value).unwrap();
+
+    buf[..n].to_vec()
+}
+
+#[test]
+fn none() {
+    let options = Options::new().with_envelope(Envelope::None);
+    assert_eq!(serialize(options, &Sample { a: 1, b: 2 }), [1, 0, 2]);
+}
+
+#[test]
+fn strukt() {
+    let options = Options::new().with_envelope(Envelope::Structs);
+    let sample = Sample { a: 1, b: 2 };
+
+    assert_eq!(serialize(options, &sample), [3, 1, 0, 2]);
+    assert_eq!(slice(options, &sample), [3, 1, 0, 2]);
+}
+
+#[test]
+fn nested() {
+    let options = Options::new().with_envelope(Envelope::Structs);
+    let outer = Outer {
+        x: 7,
+        inner: Sample { a: 1, b: 2 },
+    };
+
+    assert_eq!(serialize(options, &outer), [5, 7, 3, 1, 0, 2]);
+    assert_eq!(slice(options, &outer), [5, 7, 3, 1, 0, 2]);
+}
+
+#[test]
+fn varint() {
+    let options = Options::new()
+        .with_varint()
+        .with_envelope(Envelope::Structs);
+
+    assert_eq!(serialize(options, &Sample { a: 1, b: 2 }), [2, 1, 2]);
+}
+
+#[test]
+fn structs_only() {
+    let options = Options::new().with_envelope(Envelope::Structs);
+
+    assert_eq!(serialize(options, &Enum::A), [0, 0, 0, 0]);
+    assert_eq!(serialize(options, &Enum::B(1)), [0, 0, 0, 1, 1]);
+    assert_eq!(serialize(options, &Enum::C(1, 2)), [0, 0, 0, 2, 1, 2]);
+    assert_eq!(serialize(options, &Enum::D { x: 1 }), [0, 0, 0, 3, 1]);
+}
+
+#[test]
+fn all() {
+    let options = Options::new().with_envelope(Envelope::All);
+
+    for (value, expected) in [
+        (Enum::A, vec![0, 0, 0, 0, 0]),
+        (Enum::B(1), vec![0, 0, 0, 1, 1, 1]),
+        (Enum::C(1, 2), vec![0, 0, 0, 2, 2, 1, 2]),
+        (Enum::D { x: 1 }, vec![0, 0, 0, 3, 1, 1]),
+    ] {
+        assert_eq!(serialize(options, &value), expected);
+        assert_eq!(slice(options, &value), expected);
+    }
+}
+
+#[test]
+fn tagged_fields() {
+    let options = Options::new()
+        .with_field_names()
+        .with_envelope(Envelope::Structs);
+
+    assert_eq!(
+        serialize(options, &Sample { a: 1, b: 2 }),
+        [10, 2, 1, b'a', 1, 1, 1, b'b', 2, 0, 2]
+    );
+}
+
+#[test]
+fn self_describing() {
+    let options = Options::new()
+        .with_self_describing()
+        .with_envelope(Envelope::All);
+
+    assert_eq!(
+        serialize(options, &Sample { a: 1, b: 2 }),
+        [20, 2, 15, 1, b'a', 7, 1, 15, 1, b'b', 8, 0, 2]
+    );
+}
+
+#[test]
+fn overflow() {
+    #[derive(Serialize)]
+    struct Large {
+        a: [u64; 32],
+    }
+
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .with_envelope(Envelope::Structs)
+        .build_writer(vec![]);
+
+    let err = writer.serialize(&Large { a: [0; 32] }).unwrap_err();
+    assert_error!(err, Error::LengthOverflow(|n| n == 256));
+}
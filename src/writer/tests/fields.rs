@@ -0,0 +1,191 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::options::{Fields, Options};
+use crate::{assert_error, Width};
+
+#[derive(Serialize)]
+struct Sample {
+    a: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    b: Option<u8>,
+    c: u16,
+}
+
+#[derive(Serialize)]
+enum Enum {
+    V { x: u8 },
+}
+
+fn serialize<T: Serialize>(options: Options, value: &T) -> Vec<u8> {
+    super::serialize(options.with_length_width(Width::U8), value)
+}
+
+#[test]
+fn positional() {
+    let sample = Sample {
+        a: 1,
+        b: Some(2),
+        c: 3,
+    };
+
+    assert_eq!(serialize(Options::new(), &sample), [1, 1, 2, 0, 3]);
+    assert_eq!(
+        serialize(
+            Options::new().with_field_names().with_positional_fields(),
+            &sample
+        ),
+        [1, 1, 2, 0, 3]
+    );
+}
+
+#[test]
+fn names() {
+    let sample = Sample {
+        a: 1,
+        b: Some(2),
+        c: 3,
+    };
+
+    #[rustfmt::skip]
+    assert_eq!(
+        serialize(Options::new().with_field_names(), &sample),
+        [
+            3,
+            1, b'a', 1, 1,
+            1, b'b', 2, 1, 2,
+            1, b'c', 2, 0, 3,
+        ]
+    );
+}
+
+#[test]
+fn indices() {
+    let sample = Sample {
+        a: 1,
+        b: Some(2),
+        c: 3,
+    };
+
+    #[rustfmt::skip]
+    assert_eq!(
+        serialize(Options::new().with_field_indices(), &sample),
+        [
+            3,
+            0, 0, 0, 0, 1, 1,
+            0, 0, 0, 1, 2, 1, 2,
+            0, 0, 0, 2, 2, 0, 3,
+        ]
+    );
+}
+
+#[test]
+fn skipped_field() {
+    let sample = Sample {
+        a: 1,
+        b: None,
+        c: 3,
+    };
+
+    #[rustfmt::skip]
+    assert_eq!(
+        serialize(Options::new().with_field_indices(), &sample),
+        [
+            2,
+            0, 0, 0, 0, 1, 1,
+            0, 0, 0, 2, 2, 0, 3,
+        ]
+    );
+}
+
+#[test]
+fn struct_variant() {
+    assert_eq!(
+        serialize(Options::new().with_field_names(), &Enum::V { x: 7 }),
+        [0, 0, 0, 0, 1, 1, b'x', 1, 7]
+    );
+}
+
+#[test]
+fn self_describing() {
+    // the fields are always encoded with their names
+    for fields in [Fields::Positional, Fields::Names, Fields::Indices] {
+        let options = Options::new().with_self_describing().with_fields(fields);
+
+        #[rustfmt::skip]
+        assert_eq!(
+            serialize(options, &Enum::V { x: 7 }),
+            [
+                20, 1,
+                10, 0, 0, 0, 0, 0, 0, 0, 0,
+                20, 1,
+                15, 1, b'x', 7, 7,
+            ]
+        );
+    }
+}
+
+#[test]
+fn varint() {
+    let options = Options::new().with_varint().with_field_indices();
+
+    assert_eq!(serialize(options, &Enum::V { x: 7 }), [0, 1, 0, 1, 7]);
+}
+
+#[test]
+fn slice() {
+    let sample = Sample {
+        a: 1,
+        b: Some(2),
+        c: 3,
+    };
+    let mut buf = [0; 16];
+
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .with_field_names()
+        .build_writer(buf.as_mut_slice());
+    assert_eq!(writer.serialize(&sample).unwrap(), 15);
+    assert_eq!(
+        buf[..15],
+        [3, 1, b'a', 1, 1, 1, b'b', 2, 1, 2, 1, b'c', 2, 0, 3]
+    );
+}
+
+#[test]
+fn value_overflow() {
+    #[derive(Serialize)]
+    struct Large {
+        a: Vec<u8>,
+    }
+
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .with_field_names()
+        .build_writer(vec![]);
+
+    let err = writer.serialize(&Large { a: vec![0; 255] }).unwrap_err();
+    assert_error!(err, Error::LengthOverflow(|n| n == 256));
+}
@@ -0,0 +1,84 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::Options;
+use crate::writer::{serialized_size, to_vec, to_writer, Writer};
+
+#[test]
+fn to_vec_ok() {
+    assert_eq!(to_vec(&4711u16).unwrap(), [0x12, 0x67]);
+}
+
+#[test]
+fn to_writer_ok() {
+    let mut buf = vec![b'x'];
+
+    assert_eq!(to_writer(&mut buf, &4711u16).unwrap(), 2);
+    assert_eq!(buf, [b'x', 0x12, 0x67]);
+}
+
+#[test]
+fn options() {
+    let options = Options::new().with_little_endian();
+
+    assert_eq!(options.to_vec(&4711u16).unwrap(), [0x67, 0x12]);
+
+    let mut buf = vec![];
+    assert_eq!(options.to_writer(&mut buf, &4711u16).unwrap(), 2);
+    assert_eq!(buf, [0x67, 0x12]);
+}
+
+#[test]
+fn to_writer_nospace() {
+    let mut buf = [0; 1];
+
+    let err = to_writer(buf.as_mut_slice(), &4711u16).unwrap_err();
+    assert_error!(err, Error::NoSpace(|cause| cause.is_some()));
+}
+
+#[test]
+fn serialized_size_ok() {
+    #[derive(Serialize)]
+    struct Struct {
+        f1: u16,
+        f2: Vec<u32>,
+        f3: Option<String>,
+    }
+
+    let value = Struct {
+        f1: 4711,
+        f2: vec![1, 2, 3],
+        f3: Some("abc".to_string()),
+    };
+
+    let size = serialized_size(&value).unwrap();
+    assert_eq!(size, 2 + 8 + 12 + 1 + 8 + 3);
+
+    let mut buf = vec![0; size];
+    let mut writer = Writer::new(buf.as_mut_slice());
+    assert_eq!(writer.serialize(&value).unwrap(), size);
+    assert!(writer.as_ref().is_empty());
+}
@@ -0,0 +1,60 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use crate::options::Options;
+use crate::writer::Writer;
+
+#[test]
+fn position() {
+    let mut writer = Writer::new(vec![]);
+    assert_eq!(writer.position(), 0);
+
+    writer.write_u8(1).unwrap();
+    assert_eq!(writer.position(), 1);
+
+    writer.write_u32(2).unwrap();
+    assert_eq!(writer.position(), 5);
+
+    writer.serialize(&"abc").unwrap();
+    assert_eq!(writer.position(), 16);
+
+    writer.write_bytes(&[1, 2]).unwrap();
+    assert_eq!(writer.position(), 18);
+}
+
+#[test]
+fn varint() {
+    let mut writer = Options::new().with_varint().build_writer(vec![]);
+
+    writer.write_u32(128).unwrap();
+    assert_eq!(writer.position(), 2);
+}
+
+#[test]
+fn no_space() {
+    let mut buf = [0; 3];
+    let mut writer = Writer::new(buf.as_mut_slice());
+
+    writer.write_u16(1).unwrap();
+    writer.write_u16(2).unwrap_err();
+    assert_eq!(writer.position(), 2);
+}
@@ -0,0 +1,157 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::options::Options;
+
+#[derive(Serialize)]
+struct Sample {
+    a: u8,
+    b: bool,
+}
+
+#[derive(Serialize)]
+enum Enum {
+    A,
+    B(u16),
+    C(u8, u8),
+    D { x: u8 },
+}
+
+fn serialize<T: Serialize>(value: &T) -> Vec<u8> {
+    super::serialize(Options::new().with_self_describing(), value)
+}
+
+#[test]
+fn primitives() {
+    assert_eq!(serialize(&true), [1, 1]);
+    assert_eq!(serialize(&-1i8), [2, 0xFF]);
+    assert_eq!(serialize(&1i16), [3, 0, 1]);
+    assert_eq!(serialize(&1i32), [4, 0, 0, 0, 1]);
+    assert_eq!(serialize(&1i64), [5, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(serialize(&1i128)[..2], [6, 0]);
+    assert_eq!(serialize(&1u8), [7, 1]);
+    assert_eq!(serialize(&1u16), [8, 0, 1]);
+    assert_eq!(serialize(&1u32), [9, 0, 0, 0, 1]);
+    assert_eq!(serialize(&1u64), [10, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(serialize(&1u128).len(), 17);
+    assert_eq!(serialize(&1.0f32), [12, 0x3F, 0x80, 0, 0]);
+    assert_eq!(serialize(&1.0f64)[..3], [13, 0x3F, 0xF0]);
+    assert_eq!(serialize(&'a'), [14, 0, 0, 0, b'a']);
+}
+
+#[test]
+fn str_bytes() {
+    assert_eq!(serialize(&"ab"), [15, 0, 0, 0, 0, 0, 0, 0, 2, b'a', b'b']);
+
+    let mut writer = Options::new().with_self_describing().build_writer(vec![]);
+    assert_eq!(writer.write_bytes(&[]).unwrap(), 0);
+    serde::Serializer::serialize_bytes(&mut writer, &[1, 2]).unwrap();
+    assert_eq!(writer.into_target(), [16, 0, 0, 0, 0, 0, 0, 0, 2, 1, 2]);
+}
+
+#[test]
+fn option() {
+    assert_eq!(serialize(&None::<u8>), [17]);
+    assert_eq!(serialize(&Some(1u8)), [18, 7, 1]);
+}
+
+#[test]
+fn unit() {
+    assert_eq!(serialize(&()), [0]);
+}
+
+#[test]
+fn seq() {
+    assert_eq!(
+        serialize(&vec![1u8, 2]),
+        [19, 0, 0, 0, 0, 0, 0, 0, 2, 7, 1, 7, 2]
+    );
+    assert_eq!(
+        serialize(&(1u8, true)),
+        [19, 0, 0, 0, 0, 0, 0, 0, 2, 7, 1, 1, 1]
+    );
+}
+
+#[test]
+fn map() {
+    let map = BTreeMap::from([(1u8, 2u8)]);
+    assert_eq!(serialize(&map), [20, 0, 0, 0, 0, 0, 0, 0, 1, 7, 1, 7, 2]);
+}
+
+#[test]
+fn strukt() {
+    #[rustfmt::skip]
+    assert_eq!(
+        serialize(&Sample { a: 1, b: false }),
+        [
+            20, 0, 0, 0, 0, 0, 0, 0, 2,
+            15, 0, 0, 0, 0, 0, 0, 0, 1, b'a', 7, 1,
+            15, 0, 0, 0, 0, 0, 0, 0, 1, b'b', 1, 0,
+        ]
+    );
+}
+
+#[test]
+fn variants() {
+    const ENTRY: [u8; 9] = [20, 0, 0, 0, 0, 0, 0, 0, 1];
+
+    assert_eq!(
+        serialize(&Enum::A),
+        [&ENTRY[..], &[10, 0, 0, 0, 0, 0, 0, 0, 0, 0]].concat()
+    );
+    assert_eq!(
+        serialize(&Enum::B(1)),
+        [&ENTRY[..], &[10, 0, 0, 0, 0, 0, 0, 0, 1, 8, 0, 1]].concat()
+    );
+    #[rustfmt::skip]
+    assert_eq!(
+        serialize(&Enum::C(1, 2)),
+        [
+            &ENTRY[..],
+            &[10, 0, 0, 0, 0, 0, 0, 0, 2, 19, 0, 0, 0, 0, 0, 0, 0, 2, 7, 1, 7, 2],
+        ]
+        .concat()
+    );
+    #[rustfmt::skip]
+    assert_eq!(
+        serialize(&Enum::D { x: 1 }),
+        [
+            &ENTRY[..],
+            &[10, 0, 0, 0, 0, 0, 0, 0, 3, 20, 0, 0, 0, 0, 0, 0, 0, 1],
+            &[15, 0, 0, 0, 0, 0, 0, 0, 1, b'x', 7, 1],
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn compact() {
+    let mut writer = Options::new()
+        .with_self_describing()
+        .with_compact()
+        .build_writer(vec![]);
+    writer.serialize(&Some(1u8)).unwrap();
+    assert_eq!(writer.into_target(), [1, 1]);
+}
@@ -20,11 +20,12 @@
 // FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
 // IN THE SOFTWARE.
 
+use serde::ser::{SerializeMap, Serializer};
 use serde::Serialize;
 use std::collections::HashMap;
 
 use crate::error::Error;
-use crate::{assert_error_eq, Writer};
+use crate::{assert_error, Writer};
 
 #[test]
 fn bool() {
@@ -125,16 +126,97 @@ fn u64() {
 
 #[test]
 fn i128() {
-    let mut writer = Writer::new(vec![]);
-    let err = writer.serialize(&0i128).unwrap_err();
-    assert_error_eq!(err, Error::Serde(|msg| "i128 is not supported"));
+    for (n, buf) in [
+        (
+            -1i128,
+            [
+                0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xff,
+            ],
+        ),
+        (
+            0,
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ],
+        ),
+        (
+            1,
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x01,
+            ],
+        ),
+    ] {
+        let mut writer = Writer::new(vec![]);
+        assert_eq!(writer.serialize(&n).unwrap(), buf.len());
+        assert_eq!(writer.as_ref().as_ref(), buf);
+    }
 }
 
 #[test]
 fn u128() {
-    let mut writer = Writer::new(vec![]);
-    let err = writer.serialize(&0u128).unwrap_err();
-    assert_error_eq!(err, Error::Serde(|msg| "u128 is not supported"));
+    for (n, buf) in [
+        (
+            0u128,
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ],
+        ),
+        (
+            1,
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x01,
+            ],
+        ),
+        (
+            0x0102030405060708090A0B0C0D0E0F10,
+            [
+                0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+                0x0F, 0x10,
+            ],
+        ),
+    ] {
+        let mut writer = Writer::new(vec![]);
+        assert_eq!(writer.serialize(&n).unwrap(), buf.len());
+        assert_eq!(writer.as_ref().as_ref(), buf);
+    }
+}
+
+#[test]
+fn f32() {
+    for (n, buf) in [
+        (1.0f32, [0x3F, 0x80, 0x00, 0x00]),
+        (-2.0, [0xC0, 0x00, 0x00, 0x00]),
+        (0.0, [0x00, 0x00, 0x00, 0x00]),
+        (-0.0, [0x80, 0x00, 0x00, 0x00]),
+        (f32::from_bits(0x7FC01234), [0x7F, 0xC0, 0x12, 0x34]),
+    ] {
+        let mut writer = Writer::new(vec![]);
+        assert_eq!(writer.serialize(&n).unwrap(), buf.len());
+        assert_eq!(writer.as_ref().as_ref(), buf);
+    }
+}
+
+#[test]
+fn f64() {
+    for (n, buf) in [
+        (1.0f64, [0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+        (-2.0, [0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+        (0.0, [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+        (-0.0, [0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+        (
+            f64::from_bits(0x7FF8000000001234),
+            [0x7F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x12, 0x34],
+        ),
+    ] {
+        let mut writer = Writer::new(vec![]);
+        assert_eq!(writer.serialize(&n).unwrap(), buf.len());
+        assert_eq!(writer.as_ref().as_ref(), buf);
+    }
 }
 
 #[test]
@@ -314,6 +396,72 @@ fn map() {
     }
 }
 
+#[test]
+fn map_split() {
+    enum Call {
+        Entry(u8, u16),
+        Key(u8),
+        Value(u16),
+    }
+
+    struct SplitMap(Vec<Call>);
+
+    impl Serialize for SplitMap {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let len = self
+                .0
+                .iter()
+                .filter(|call| !matches!(call, Call::Value(_)))
+                .count();
+            let mut map = serializer.serialize_map(Some(len))?;
+
+            for call in self.0.iter() {
+                match call {
+                    Call::Entry(k, v) => map.serialize_entry(k, v)?,
+                    Call::Key(k) => map.serialize_key(k)?,
+                    Call::Value(v) => map.serialize_value(v)?,
+                }
+            }
+
+            map.end()
+        }
+    }
+
+    let mut writer = Writer::new(vec![]);
+    let map = SplitMap(vec![Call::Entry(1, 4711), Call::Entry(2, 666)]);
+    assert_eq!(writer.serialize(&map).unwrap(), 14);
+    let entries = writer.into_target();
+
+    let mut writer = Writer::new(vec![]);
+    let map = SplitMap(vec![
+        Call::Key(1),
+        Call::Value(4711),
+        Call::Key(2),
+        Call::Value(666),
+    ]);
+    assert_eq!(writer.serialize(&map).unwrap(), 14);
+    assert_eq!(writer.as_ref().as_ref(), entries);
+    assert_eq!(
+        writer.as_ref().as_ref(),
+        [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x01, 0x12, 0x67, 0x02, 0x02, 0x9A]
+    );
+
+    let mut writer = Writer::new(vec![]);
+    let map = SplitMap(vec![Call::Value(4711)]);
+    let err = writer.serialize(&map).unwrap_err();
+    assert_error!(err, Error::MissingKey);
+
+    let mut writer = Writer::new(vec![]);
+    let map = SplitMap(vec![Call::Key(1), Call::Key(2)]);
+    let err = writer.serialize(&map).unwrap_err();
+    assert_error!(err, Error::MissingValue);
+
+    let mut writer = Writer::new(vec![]);
+    let map = SplitMap(vec![Call::Key(1)]);
+    let err = writer.serialize(&map).unwrap_err();
+    assert_error!(err, Error::MissingValue);
+}
+
 #[test]
 fn unit() {
     let mut writer = Writer::new(vec![]);
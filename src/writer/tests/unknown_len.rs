@@ -0,0 +1,211 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use std::io::Cursor;
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::{Options, Width};
+use crate::target::WriteTarget;
+use crate::writer::Writer;
+
+// An iterator without an exact size hint, thus the length is unknown.
+fn elems(n: u8) -> impl Iterator<Item = u8> {
+    (1..=n).filter(|_| true)
+}
+
+struct Seq(u8);
+
+impl Serialize for Seq {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(elems(self.0))
+    }
+}
+
+#[derive(Serialize)]
+struct Inner {
+    a: u8,
+    b: u16,
+}
+
+#[derive(Serialize)]
+struct Outer {
+    x: u8,
+    #[serde(flatten)]
+    inner: Inner,
+}
+
+#[test]
+fn seq_vec() {
+    let mut writer = Writer::new(vec![]);
+    assert_eq!(writer.collect_seq(elems(3)).unwrap(), 11);
+    assert_eq!(writer.position(), 11);
+    assert_eq!(writer.into_target(), [0, 0, 0, 0, 0, 0, 0, 3, 1, 2, 3]);
+}
+
+#[test]
+fn seq_empty() {
+    let mut writer = Writer::new(vec![]);
+    assert_eq!(writer.collect_seq(elems(0)).unwrap(), 8);
+    assert_eq!(writer.into_target(), [0; 8]);
+}
+
+#[test]
+fn seq_slice() {
+    let mut buf = [0xFF; 12];
+    let mut writer = Writer::new(buf.as_mut_slice());
+    assert_eq!(writer.collect_seq(elems(3)).unwrap(), 11);
+    assert_eq!(writer.position(), 11);
+    assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0, 3, 1, 2, 3, 0xFF]);
+}
+
+#[test]
+fn seq_slice_no_space() {
+    let mut buf = [0xFF; 10];
+    let mut writer = Writer::new(buf.as_mut_slice());
+    let err = writer.collect_seq(elems(3)).unwrap_err();
+    assert_error!(err, Error::NoSpace(|cause| cause.is_none()));
+}
+
+#[test]
+fn seq_write() {
+    let mut writer = Writer::new(WriteTarget::new(Cursor::new(vec![])));
+    assert_eq!(writer.collect_seq(elems(2)).unwrap(), 10);
+    assert_eq!(
+        writer.into_target().into_inner().into_inner(),
+        [0, 0, 0, 0, 0, 0, 0, 2, 1, 2]
+    );
+}
+
+#[test]
+fn seq_varint() {
+    let mut writer = Options::new().with_varint_prefixes().build_writer(vec![]);
+    assert_eq!(writer.collect_seq(elems(3)).unwrap(), 4);
+    assert_eq!(writer.into_target(), [3, 1, 2, 3]);
+}
+
+#[test]
+fn seq_nested() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .build_writer(vec![]);
+    let mut seq = writer.serialize_seq(None).unwrap();
+    seq.serialize_element(&1u8).unwrap();
+    seq.serialize_element(&Seq(2)).unwrap();
+    seq.serialize_element(&Seq(0)).unwrap();
+    assert_eq!(SerializeSeq::end(seq).unwrap(), 6);
+    assert_eq!(writer.into_target(), [3, 1, 2, 1, 2, 0]);
+}
+
+#[test]
+fn seq_nested_buffered() {
+    let mut buf = [0xFF; 7];
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .build_writer(buf.as_mut_slice());
+    assert_eq!(writer.serialize(&(Seq(1), Seq(2))).unwrap(), 5);
+    assert_eq!(buf, [1, 1, 2, 1, 2, 0xFF, 0xFF]);
+}
+
+#[test]
+fn seq_overflow() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .build_writer(vec![]);
+    let err = writer.collect_seq(elems(255).chain(elems(1))).unwrap_err();
+    assert_error!(err, Error::LengthOverflow(|n| n == 256));
+}
+
+#[test]
+fn seq_overflow_buffered() {
+    let mut writer = Options::new()
+        .with_varint_prefixes()
+        .with_length_width(Width::U8)
+        .build_writer(WriteTarget::new(vec![]));
+    let err = writer.collect_seq(elems(255).chain(elems(1))).unwrap_err();
+    assert_error!(err, Error::LengthOverflow(|n| n == 256));
+}
+
+#[test]
+fn seq_reuse_after_error() {
+    let mut writer = Options::new()
+        .with_max_depth(1)
+        .build_writer(WriteTarget::new(vec![]));
+
+    let mut seq = writer.serialize_seq(None).unwrap();
+    seq.serialize_element(&1u8).unwrap();
+    let err = seq.serialize_element(&Some(2u8)).unwrap_err();
+    assert_error!(err, Error::DepthExceeded(|n| n == 1));
+    drop(seq);
+    assert_eq!(writer.position(), 0);
+
+    assert_eq!(writer.serialize(&1u8).unwrap(), 1);
+    assert_eq!(writer.position(), 1);
+    assert_eq!(writer.into_target().into_inner(), [1]);
+}
+
+#[test]
+fn map() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U16)
+        .build_writer(vec![]);
+    let mut map = writer.serialize_map(None).unwrap();
+    map.serialize_entry(&1u8, &2u8).unwrap();
+    map.serialize_entry(&3u8, &4u8).unwrap();
+    assert_eq!(SerializeMap::end(map).unwrap(), 6);
+    assert_eq!(writer.into_target(), [0, 2, 1, 2, 3, 4]);
+}
+
+#[test]
+fn map_buffered() {
+    let mut writer = Options::new()
+        .with_varint_prefixes()
+        .build_writer(WriteTarget::new(vec![]));
+    let mut map = writer.serialize_map(None).unwrap();
+    map.serialize_entry(&1u8, &2u8).unwrap();
+    assert_eq!(SerializeMap::end(map).unwrap(), 3);
+    assert_eq!(writer.into_target().into_inner(), [1, 1, 2]);
+}
+
+#[test]
+fn flatten() {
+    let outer = Outer {
+        x: 1,
+        inner: Inner { a: 2, b: 3 },
+    };
+
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .build_writer(vec![]);
+    assert_eq!(writer.serialize(&outer).unwrap(), 11);
+    assert_eq!(
+        writer.into_target(),
+        [3, 1, b'x', 1, 1, b'a', 2, 1, b'b', 0, 3]
+    );
+}
+
+#[test]
+fn serialized_size() {
+    assert_eq!(Options::new().serialized_size(&Seq(3)).unwrap(), 11);
+}
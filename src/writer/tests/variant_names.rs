@@ -0,0 +1,94 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+
+use crate::options::Options;
+
+#[derive(Serialize)]
+enum Enum {
+    A,
+    Bc(u8),
+    D(u8, u8),
+    E { x: u8 },
+}
+
+fn serialize(options: Options, value: &Enum) -> Vec<u8> {
+    super::serialize(options.with_variant_names(), value)
+}
+
+#[test]
+fn unit_variant() {
+    assert_eq!(
+        serialize(Options::new(), &Enum::A),
+        [0, 0, 0, 0, 0, 0, 0, 1, b'A']
+    );
+}
+
+#[test]
+fn newtype_variant() {
+    assert_eq!(
+        serialize(Options::new(), &Enum::Bc(7)),
+        [0, 0, 0, 0, 0, 0, 0, 2, b'B', b'c', 7]
+    );
+}
+
+#[test]
+fn tuple_variant() {
+    assert_eq!(
+        serialize(Options::new(), &Enum::D(1, 2)),
+        [0, 0, 0, 0, 0, 0, 0, 1, b'D', 1, 2]
+    );
+}
+
+#[test]
+fn struct_variant() {
+    assert_eq!(
+        serialize(Options::new(), &Enum::E { x: 1 }),
+        [0, 0, 0, 0, 0, 0, 0, 1, b'E', 1]
+    );
+}
+
+#[test]
+fn length_width() {
+    let options = Options::new().with_length_width(crate::Width::U8);
+    assert_eq!(serialize(options, &Enum::Bc(7)), [2, b'B', b'c', 7]);
+}
+
+#[test]
+fn self_describing() {
+    let options = Options::new().with_self_describing();
+    assert_eq!(
+        serialize(options, &Enum::A),
+        [20, 0, 0, 0, 0, 0, 0, 0, 1, 15, 0, 0, 0, 0, 0, 0, 0, 1, b'A', 0]
+    );
+}
+
+#[test]
+fn indices() {
+    let mut writer = Options::new()
+        .with_variant_names()
+        .with_variant_indices()
+        .build_writer(vec![]);
+    writer.serialize(&Enum::Bc(7)).unwrap();
+    assert_eq!(writer.into_target(), [0, 0, 0, 1, 7]);
+}
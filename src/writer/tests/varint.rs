@@ -0,0 +1,144 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::Serialize;
+
+use crate::options::{Int, Options};
+
+#[test]
+fn u16() {
+    for (n, buf) in [
+        (0u16, vec![0x00]),
+        (1, vec![0x01]),
+        (127, vec![0x7F]),
+        (128, vec![0x80, 0x01]),
+        (4711, vec![0xE7, 0x24]),
+        (u16::MAX, vec![0xFF, 0xFF, 0x03]),
+    ] {
+        let mut writer = Options::new().with_varint().build_writer(vec![]);
+        assert_eq!(writer.write_u16(n).unwrap(), buf.len());
+        assert_eq!(writer.into_target(), buf);
+    }
+}
+
+#[test]
+fn with_int() {
+    for (int, buf) in [
+        (Int::Fixed, vec![0x12, 0x67]),
+        (Int::VarintPrefixes, vec![0x12, 0x67]),
+        (Int::Varint, vec![0xE7, 0x24]),
+    ] {
+        let mut writer = Options::new().with_int(int).build_writer(vec![]);
+        assert_eq!(writer.write_u16(4711).unwrap(), buf.len());
+        assert_eq!(writer.into_target(), buf);
+    }
+}
+
+#[test]
+fn u64() {
+    let mut writer = Options::new().with_varint().build_writer(vec![]);
+    assert_eq!(writer.write_u64(u64::MAX).unwrap(), 10);
+    assert_eq!(
+        writer.into_target(),
+        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]
+    );
+}
+
+#[test]
+fn u128() {
+    let mut buf = vec![0xFF; 18];
+    buf.push(0x03);
+
+    let mut writer = Options::new().with_varint().build_writer(vec![]);
+    assert_eq!(writer.write_u128(u128::MAX).unwrap(), 19);
+    assert_eq!(writer.into_target(), buf);
+}
+
+#[test]
+fn i32() {
+    for (n, buf) in [
+        (0i32, vec![0x00]),
+        (-1, vec![0x01]),
+        (1, vec![0x02]),
+        (-2, vec![0x03]),
+        (i32::MAX, vec![0xFE, 0xFF, 0xFF, 0xFF, 0x0F]),
+        (i32::MIN, vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F]),
+    ] {
+        let mut writer = Options::new().with_varint().build_writer(vec![]);
+        assert_eq!(writer.write_i32(n).unwrap(), buf.len());
+        assert_eq!(writer.into_target(), buf);
+    }
+}
+
+#[test]
+fn u8() {
+    let mut writer = Options::new().with_varint().build_writer(vec![]);
+    assert_eq!(writer.write_u8(0xFF).unwrap(), 1);
+    assert_eq!(writer.into_target(), [0xFF]);
+}
+
+#[test]
+fn prefixes() {
+    #[derive(Serialize)]
+    enum Enum {
+        #[allow(dead_code)]
+        V1,
+        V2(String, u16),
+    }
+
+    let value = Enum::V2("abc".to_string(), 4711);
+
+    let mut writer = Options::new().with_varint_prefixes().build_writer(vec![]);
+    assert_eq!(writer.serialize(&value).unwrap(), 7);
+    assert_eq!(
+        writer.into_target(),
+        [0x01, 0x03, b'a', b'b', b'c', 0x12, 0x67]
+    );
+
+    let mut writer = Options::new().with_varint().build_writer(vec![]);
+    assert_eq!(writer.serialize(&value).unwrap(), 7);
+    assert_eq!(
+        writer.into_target(),
+        [0x01, 0x03, b'a', b'b', b'c', 0xE7, 0x24]
+    );
+}
+
+#[test]
+fn serialized_size() {
+    let value = vec![1u32, 128];
+
+    assert_eq!(Options::new().serialized_size(&value).unwrap(), 16);
+    assert_eq!(
+        Options::new()
+            .with_varint_prefixes()
+            .serialized_size(&value)
+            .unwrap(),
+        9
+    );
+    assert_eq!(
+        Options::new()
+            .with_varint()
+            .serialized_size(&value)
+            .unwrap(),
+        4
+    );
+}
@@ -0,0 +1,156 @@
+// MIT License
+//
+// Copyright (c) 2023 Robin Doer
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to
+// deal in the Software without restriction, including without limitation the
+// rights to use, copy, modify, merge, publish, distribute, sublicense, and/or
+// sell copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING
+// FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS
+// IN THE SOFTWARE.
+
+use serde::{Serialize, Serializer};
+
+use crate::assert_error;
+use crate::error::Error;
+use crate::options::{Options, Width};
+
+#[test]
+fn u8() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .build_writer(vec![]);
+    assert_eq!("abc".serialize(&mut writer).unwrap(), 4);
+    assert_eq!(writer.into_target(), [3, b'a', b'b', b'c']);
+}
+
+#[test]
+fn u8_max() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .build_writer(vec![]);
+    assert_eq!(vec![0u8; 255].serialize(&mut writer).unwrap(), 256);
+    assert_eq!(writer.into_target()[0], 255);
+}
+
+#[test]
+fn u8_overflow() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U8)
+        .build_writer(vec![]);
+    let err = vec![0u8; 256].serialize(&mut writer).unwrap_err();
+    assert_error!(err, Error::LengthOverflow(|n| n == 256));
+}
+
+#[test]
+fn u16() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U16)
+        .build_writer(vec![]);
+    assert_eq!(vec![1u8, 2].serialize(&mut writer).unwrap(), 4);
+    assert_eq!(writer.into_target(), [0, 2, 1, 2]);
+}
+
+#[test]
+fn u16_little_endian() {
+    let mut writer = Options::new()
+        .with_little_endian()
+        .with_length_width(Width::U16)
+        .build_writer(vec![]);
+    assert_eq!(vec![1u8, 2].serialize(&mut writer).unwrap(), 4);
+    assert_eq!(writer.into_target(), [2, 0, 1, 2]);
+}
+
+#[test]
+fn u16_overflow() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U16)
+        .build_writer(vec![]);
+    let err = vec![0u8; 65536].serialize(&mut writer).unwrap_err();
+    assert_error!(err, Error::LengthOverflow(|n| n == 65536));
+}
+
+#[test]
+fn u32() {
+    let mut writer = Options::new()
+        .with_length_width(Width::U32)
+        .build_writer(vec![]);
+    assert_eq!("a".serialize(&mut writer).unwrap(), 5);
+    assert_eq!(writer.into_target(), [0, 0, 0, 1, b'a']);
+}
+
+#[test]
+fn varint_u8_overflow() {
+    let mut writer = Options::new()
+        .with_varint_prefixes()
+        .with_length_width(Width::U8)
+        .build_writer(vec![]);
+    let err = vec![0u8; 256].serialize(&mut writer).unwrap_err();
+    assert_error!(err, Error::LengthOverflow(|n| n == 256));
+}
+
+#[derive(Serialize)]
+enum Enum {
+    A,
+    B(u8),
+}
+
+#[test]
+fn tag_u8() {
+    let mut writer = Options::new()
+        .with_tag_width(Width::U8)
+        .build_writer(vec![]);
+    assert_eq!(Enum::A.serialize(&mut writer).unwrap(), 1);
+    assert_eq!(Enum::B(7).serialize(&mut writer).unwrap(), 2);
+    assert_eq!(writer.into_target(), [0, 1, 7]);
+}
+
+#[test]
+fn tag_u16() {
+    let mut writer = Options::new()
+        .with_tag_width(Width::U16)
+        .build_writer(vec![]);
+    assert_eq!(Enum::B(7).serialize(&mut writer).unwrap(), 3);
+    assert_eq!(writer.into_target(), [0, 1, 7]);
+}
+
+#[test]
+fn tag_u64() {
+    let mut writer = Options::new()
+        .with_tag_width(Width::U64)
+        .build_writer(vec![]);
+    assert_eq!(Enum::A.serialize(&mut writer).unwrap(), 8);
+    assert_eq!(writer.into_target(), [0; 8]);
+}
+
+#[test]
+fn tag_u8_overflow() {
+    let mut writer = Options::new()
+        .with_tag_width(Width::U8)
+        .build_writer(vec![]);
+    let err = writer.serialize_unit_variant("Enum", 256, "X").unwrap_err();
+    assert_error!(err, Error::TagOverflow(|n| n == 256));
+}
+
+#[test]
+fn tag_varint_u8_overflow() {
+    let mut writer = Options::new()
+        .with_varint_prefixes()
+        .with_tag_width(Width::U8)
+        .build_writer(vec![]);
+    let err = writer
+        .serialize_newtype_variant("Enum", 300, "X", &1u8)
+        .unwrap_err();
+    assert_error!(err, Error::TagOverflow(|n| n == 300));
+}
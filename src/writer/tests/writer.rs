@@ -577,3 +577,92 @@ fn u128_slice() {
         ]
     );
 }
+
+#[test]
+fn f32_vec() {
+    let mut writer = Writer::new(vec![]);
+
+    assert_eq!(writer.write_f32(1.0).unwrap(), 4);
+    assert_eq!(*writer.as_ref(), [0x3F, 0x80, 0x00, 0x00]);
+    assert_eq!(writer.write_f32(-2.0).unwrap(), 4);
+    assert_eq!(
+        *writer.as_ref(),
+        [0x3F, 0x80, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00]
+    );
+}
+
+#[test]
+fn f32_slice() {
+    let mut buf = [b'x'; 11];
+
+    {
+        let mut writer = Writer::new(buf.as_mut_slice());
+
+        assert_eq!(writer.write_f32(1.0).unwrap(), 4);
+        assert_eq!(*writer.as_ref(), [b'x', b'x', b'x', b'x', b'x', b'x', b'x']);
+
+        assert_eq!(writer.write_f32(-2.0).unwrap(), 4);
+        assert_eq!(*writer.as_ref(), [b'x', b'x', b'x']);
+
+        let err = writer.write_f32(3.0).unwrap_err();
+        assert_error!(err, Error::NoSpace(|cause| cause.is_none()));
+        assert_eq!(*writer.as_ref(), [b'x', b'x', b'x']);
+    }
+
+    assert_eq!(
+        buf,
+        [0x3F, 0x80, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, b'x', b'x', b'x']
+    );
+}
+
+#[test]
+fn f64_vec() {
+    let mut writer = Writer::new(vec![]);
+
+    assert_eq!(writer.write_f64(1.0).unwrap(), 8);
+    assert_eq!(
+        *writer.as_ref(),
+        [0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(writer.write_f64(-2.0).unwrap(), 8);
+    assert_eq!(
+        *writer.as_ref(),
+        [
+            0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00
+        ]
+    );
+}
+
+#[test]
+fn f64_slice() {
+    let mut buf = [b'x'; 23];
+
+    {
+        let mut writer = Writer::new(buf.as_mut_slice());
+
+        assert_eq!(writer.write_f64(1.0).unwrap(), 8);
+        assert_eq!(
+            *writer.as_ref(),
+            [
+                b'x', b'x', b'x', b'x', b'x', b'x', b'x', b'x', b'x', b'x', b'x', b'x', b'x', b'x',
+                b'x'
+            ]
+        );
+
+        assert_eq!(writer.write_f64(-2.0).unwrap(), 8);
+        assert_eq!(*writer.as_ref(), [b'x', b'x', b'x', b'x', b'x', b'x', b'x']);
+
+        let err = writer.write_f64(3.0).unwrap_err();
+        assert_error!(err, Error::NoSpace(|cause| cause.is_none()));
+        assert_eq!(*writer.as_ref(), [b'x', b'x', b'x', b'x', b'x', b'x', b'x']);
+    }
+
+    assert_eq!(
+        buf,
+        [
+            0x3F, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, b'x', b'x', b'x', b'x', b'x', b'x', b'x'
+        ]
+    );
+}